@@ -1 +1,207 @@
+use crate::errors::PuzzleError;
+use crate::solver::board::Board;
+use base64::DecodeError;
+
+pub mod predictive;
 pub mod simple;
+
+// A format to store puzzles in fewer bytes
+pub trait Codec: Sync {
+    // How the codec is chosen on the command line
+    fn name(&self) -> &'static str;
+    // The tag in front of encoded puzzles. Changing the format needs a new id, so old puzzles can
+    // still be read.
+    fn id(&self) -> &'static str;
+    fn encode(&self, board: &Board) -> Result<Vec<u8>, PuzzleError>;
+    // The 81 digits of the puzzle (0 for empty cells), without checking that they fit together
+    fn decode_digits(&self, coded: &[u8]) -> Result<Vec<u8>, PuzzleError>;
+
+    fn decode(&self, coded: &[u8]) -> Result<Board, PuzzleError> {
+        // The offsets of errors would point into the decoded puzzle, not the input
+        Board::from_puzzle(self.decode_digits(coded)?).map_err(PuzzleError::without_offset)
+    }
+}
+
+// Every codec that can be used to encode or decode puzzles
+pub static CODECS: [&dyn Codec; 2] = [&simple::Simple, &predictive::Predictive];
+
+// Used when no codec is chosen, and for puzzles from before there were tags
+pub static DEFAULT_CODEC: &dyn Codec = &simple::Simple;
+
+// Encoded puzzles start with the id of the codec and a dot, like "p1."
+const TAG_SEPARATOR: char = '.';
+
+pub fn names() -> Vec<&'static str> {
+    CODECS.iter().map(|codec| codec.name()).collect()
+}
+
+pub fn by_name(name: &str) -> Result<&'static dyn Codec, String> {
+    let name = name.to_lowercase();
+    CODECS
+        .iter()
+        .copied()
+        .find(|codec| codec.name() == name)
+        .ok_or_else(|| format!("Unknown codec '{}'", name))
+}
+
+// The puzzle in base64, with the tag of the codec in front
+pub fn encode(board: &Board, codec: &dyn Codec) -> Result<String, PuzzleError> {
+    let payload = base64::encode_config(codec.encode(board)?, base64::URL_SAFE_NO_PAD);
+    Ok(format!("{}{}{}", codec.id(), TAG_SEPARATOR, payload))
+}
+
+// Reads a puzzle with the codec its tag names
+pub fn decode(puzzle: &str) -> Result<Board, PuzzleError> {
+    let (codec, coded) = unwrap(puzzle)?;
+    codec.decode(&coded)
+}
+
+// Reads the digits of a puzzle with the codec its tag names, without checking that they fit
+// together
+pub fn decode_digits(puzzle: &str) -> Result<Vec<u8>, PuzzleError> {
+    let (codec, coded) = unwrap(puzzle)?;
+    codec.decode_digits(&coded)
+}
+
+// Finds the codec of a puzzle by its tag, and decodes the base64 after it
+fn unwrap(puzzle: &str) -> Result<(&'static dyn Codec, Vec<u8>), PuzzleError> {
+    let (codec, payload) = match puzzle.split_once(TAG_SEPARATOR) {
+        Some((id, payload)) => match CODECS.iter().find(|codec| codec.id() == id) {
+            Some(&codec) => (codec, payload),
+            None => {
+                return Err(PuzzleError::Invalid {
+                    offset: Some(0),
+                    reason: format!("Unknown codec tag '{}'", id),
+                })
+            }
+        },
+        None => (DEFAULT_CODEC, puzzle),
+    };
+    let tag_length = puzzle.len() - payload.len();
+    let coded = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).map_err(|error| {
+        let offset = match error {
+            DecodeError::InvalidByte(offset, _) | DecodeError::InvalidLastSymbol(offset, _) => {
+                Some(tag_length + offset)
+            }
+            DecodeError::InvalidLength => None,
+        };
+        PuzzleError::Invalid {
+            offset,
+            reason: error.to_string(),
+        }
+    })?;
+    Ok((codec, coded))
+}
+
+// Checks that the codec decoded a whole puzzle
+fn to_digits(digits: &str) -> Result<Vec<u8>, PuzzleError> {
+    let cells = digits.chars().count();
+    if cells != 81 {
+        return Err(PuzzleError::Invalid {
+            offset: None,
+            reason: format!("The encoded puzzle has {} instead of 81 cells", cells),
+        });
+    }
+    Ok(digits
+        .chars()
+        .map(|x| x.to_digit(10).map(|x| x as u8).unwrap_or(0))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::generate::random_grid;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    const GRIDS: usize = 200;
+
+    fn puzzle(nums: Vec<u8>) -> Board {
+        Board::from_puzzle(nums).expect("Test puzzles are free of contradictions")
+    }
+
+    // Random grids with every amount of empty cells, from none to all of them
+    fn corpus() -> Vec<Board> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut ret = vec![];
+        for i in 0..GRIDS {
+            let mut nums = random_grid(&mut rng).to_nums();
+            let mut order: Vec<usize> = (0..81).collect();
+            order.shuffle(&mut rng);
+            for &cell in order.iter().take(i * 82 / GRIDS) {
+                nums[cell] = 0;
+            }
+            ret.push(puzzle(nums));
+        }
+        ret
+    }
+
+    fn assert_round_trip(board: &Board) {
+        for codec in CODECS.iter() {
+            let encoded = encode(board, *codec)
+                .unwrap_or_else(|error| panic!("{} can't encode: {}", codec.name(), error));
+            let decoded = decode(&encoded).unwrap_or_else(|error| {
+                panic!("{} can't decode {}: {}", codec.name(), encoded, error)
+            });
+            assert_eq!(
+                decoded.to_1d_string(),
+                board.to_1d_string(),
+                "{} changed the puzzle, encoded as {}",
+                codec.name(),
+                encoded
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_corpus() {
+        for board in corpus() {
+            assert_round_trip(&board);
+        }
+    }
+
+    // Runs of empty cells of every length and in every position, as both codecs treat them
+    // specially
+    #[test]
+    fn round_trip_empty_runs() {
+        let grid = random_grid(&mut StdRng::seed_from_u64(1)).to_nums();
+        for start in 0..81 {
+            for length in 1..=(81 - start).min(12) {
+                let mut nums = grid.clone();
+                for num in &mut nums[start..start + length] {
+                    *num = 0;
+                }
+                assert_round_trip(&puzzle(nums));
+            }
+        }
+    }
+
+    #[test]
+    fn untagged_puzzles_are_simple() {
+        let board = decode("mo_F_SobMB1sS3BwhvowGk0q").unwrap();
+        assert_eq!(
+            board.to_1d_string(),
+            "900800000000000500000000000020010003010000060000400070708600000000030100400000200"
+        );
+    }
+
+    // Anything could be passed in as an encoded puzzle, so broken ones must fail cleanly
+    #[test]
+    fn broken_input_is_an_error() {
+        assert!(decode("x9.AAAA").is_err());
+        assert!(decode("s1.AA*A").is_err());
+        let mut rng = StdRng::seed_from_u64(2);
+        for codec in CODECS.iter() {
+            for length in 0..48 {
+                for _ in 0..20 {
+                    let coded: Vec<u8> = (0..length).map(|_| rand::Rng::gen(&mut rng)).collect();
+                    if let Ok(board) = codec.decode(&coded) {
+                        assert_eq!(board.to_1d_string().len(), 81);
+                    }
+                }
+            }
+        }
+    }
+}
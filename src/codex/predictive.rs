@@ -1,8 +1,29 @@
-use crate::errors::UnsolvableError;
+use crate::codex::{to_digits, Codec};
+use crate::errors::PuzzleError;
 use crate::solver::board::Board;
+use crate::solver::precalc::PEERS;
 use crate::solver::solve::solve;
 use crate::tools::{BitReader, BitWriter};
-use std::error::Error;
+
+pub struct Predictive;
+
+impl Codec for Predictive {
+    fn name(&self) -> &'static str {
+        "predictive"
+    }
+
+    fn id(&self) -> &'static str {
+        "p1"
+    }
+
+    fn encode(&self, board: &Board) -> Result<Vec<u8>, PuzzleError> {
+        encode(board)
+    }
+
+    fn decode_digits(&self, coded: &[u8]) -> Result<Vec<u8>, PuzzleError> {
+        to_digits(&decode(coded.to_vec())?)
+    }
+}
 
 struct PredictorBoard {
     board: [u8; 81],
@@ -14,10 +35,6 @@ impl PredictorBoard {
             return vec![self.board[i as usize]];
         }
 
-        let row = (i / 9) * 9;
-        let col = i % 9;
-        let block = (row / 27) * 27 + col / 3 * 3;
-
         fn mask(val: u8) -> u16 {
             if val == 0 {
                 return 0;
@@ -25,23 +42,11 @@ impl PredictorBoard {
             1 << (val - 1)
         }
 
-        let mut out_mask: u16 = 0;
-
-        for j in 0..9 {
-            let col_val = self.board[(col + j * 9) as usize];
-            let row_val = self.board[(row + j) as usize];
+        let out_mask = PEERS[i as usize].iter().fold(0u16, |out_mask, peer| {
+            out_mask | mask(self.board[peer.0 as usize])
+        });
 
-            let block_row = j / 3;
-            let block_col = i % 3;
-            let block_val = self.board[(block + block_col + block_row * 9) as usize];
-
-            out_mask |= mask(col_val) | mask(row_val) | mask(block_val);
-        }
-
-        (1..10)
-            .into_iter()
-            .filter(|&x| (out_mask & mask(x)) == 0)
-            .collect()
+        (1..10).filter(|&x| (out_mask & mask(x)) == 0).collect()
     }
 
     fn set(&mut self, i: u8, val: u8) {
@@ -53,18 +58,22 @@ pub fn pattern_linear(i: u8) -> u8 {
     i
 }
 
+#[allow(dead_code)]
 pub fn pattern_c2(i: u8) -> u8 {
     i * 2 % 81
 }
 
+#[allow(dead_code)]
 pub fn pattern_c4(i: u8) -> u8 {
     i * 4 % 81
 }
 
+#[allow(dead_code)]
 pub fn pattern_c8(i: u8) -> u8 {
     ((i as u32 * 8) % 81) as u8
 }
 
+#[allow(dead_code)]
 pub fn pattern_c16(i: u8) -> u8 {
     ((i as u32 * 16) % 81) as u8
 }
@@ -74,13 +83,9 @@ const HOLE_ENCODE_MAX: u8 = 2u32.pow((HOLE_ENCODE_BITS - 1) as u32) as u8;
 const CHAIN_ENCODE_BITS: u8 = 1;
 const CHAIN_ENCODE_MAX: u8 = 1_u8;
 
-pub fn encode(string: &str) -> Result<Vec<u8>, Box<dyn Error>> {
-    let nums: Vec<u8> = string
-        .chars()
-        .map(|x| x.to_digit(10).unwrap() as u8)
-        .collect();
-    let holes: Vec<bool> = nums.iter().map(|&num| num == 0).collect();
-    let solved = solve(Board::from_puzzle(nums)?, false)?;
+fn encode(board: &Board) -> Result<Vec<u8>, PuzzleError> {
+    let holes: Vec<bool> = board.to_nums().iter().map(|&num| num == 0).collect();
+    let solved = solve(board.clone(), false)?;
     if let Some(solved) = solved {
         let solved_nums = solved.to_nums();
 
@@ -153,15 +158,18 @@ pub fn encode(string: &str) -> Result<Vec<u8>, Box<dyn Error>> {
                 (hole_length - 1) | 1 << (HOLE_ENCODE_BITS - 1),
                 HOLE_ENCODE_BITS,
             );
-            hole_length = 0;
         }
         Ok(writer.disolve_drop_zeros())
     } else {
-        Err(Box::new(UnsolvableError {}))
+        Err(PuzzleError::Unsolvable)
     }
 }
 
-pub fn decode(coded: Vec<u8>) -> String {
+fn decode(coded: Vec<u8>) -> Result<String, PuzzleError> {
+    let invalid = |reason: &str| PuzzleError::Invalid {
+        offset: None,
+        reason: reason.to_string(),
+    };
     let mut reader = BitReader::new(coded);
 
     let mut board = PredictorBoard { board: [0u8; 81] };
@@ -175,10 +183,12 @@ pub fn decode(coded: Vec<u8>) -> String {
             3 | 4 => reader.read(2),
             2 => reader.read(1),
             1 => 0,
-            _ => panic!("Encoding of broken puzzle!"),
+            _ => return Err(invalid("The encoded solution leads to a contradiction")),
         } as usize;
 
-        let num = possiblities[decoded];
+        let num = *possiblities
+            .get(decoded)
+            .ok_or_else(|| invalid("The encoded solution uses an impossible digit"))?;
 
         board.set(real_i, num);
     }
@@ -189,6 +199,9 @@ pub fn decode(coded: Vec<u8>) -> String {
         let is_hole = reader.read(1) == 1;
         if is_hole {
             let hole_size = reader.read(HOLE_ENCODE_BITS - 1) + 1;
+            if pos + hole_size > 81 {
+                return Err(invalid("The encoded holes go past the end of the puzzle"));
+            }
             for _ in 0..hole_size {
                 board.set(pos, 0);
                 pos += 1;
@@ -213,5 +226,51 @@ pub fn decode(coded: Vec<u8>) -> String {
     }
      */
 
-    board.board.map(|x| format!("{}", x)).join("")
+    Ok(board.board.map(|x| format!("{}", x)).join(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::calc::Cell;
+    use crate::solver::generate::random_grid;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    // Only the cells diagonal to r1c1 in its block are filled, which share neither its row nor its
+    // column. Scanning a single column of the block would miss them.
+    #[test]
+    fn possibilities_see_the_whole_block() {
+        let mut board = PredictorBoard { board: [0u8; 81] };
+        board.set(10, 5);
+        board.set(20, 7);
+        assert_eq!(board.possibilities(0), vec![1, 2, 3, 4, 6, 8, 9]);
+    }
+
+    // Offering a digit that a peer already has wastes bits, so the predictor has to rule out
+    // exactly the digits in the same row, column and block
+    #[test]
+    fn possibilities_exclude_peers() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let grid = random_grid(&mut rng).to_nums();
+            let mut order: Vec<u8> = (0..81).collect();
+            order.shuffle(&mut rng);
+            let mut board = PredictorBoard { board: [0u8; 81] };
+            for i in order {
+                let cell = Cell(i);
+                let taken: Vec<u8> = cell
+                    .row()
+                    .into_iter()
+                    .chain(cell.col())
+                    .chain(cell.block())
+                    .map(|peer| board.board[peer.0 as usize])
+                    .collect();
+                let expected: Vec<u8> = (1..10).filter(|digit| !taken.contains(digit)).collect();
+                assert_eq!(board.possibilities(i), expected, "{}", cell.name());
+                board.set(i, grid[i as usize]);
+            }
+        }
+    }
 }
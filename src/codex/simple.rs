@@ -1,23 +1,38 @@
+use crate::codex::{to_digits, Codec};
+use crate::errors::PuzzleError;
+use crate::solver::board::Board;
 use std::iter::Peekable;
 
-#[allow(dead_code)]
-pub fn encode(string: &str) -> Vec<u8> {
+pub struct Simple;
+
+impl Codec for Simple {
+    fn name(&self) -> &'static str {
+        "simple"
+    }
+
+    fn id(&self) -> &'static str {
+        "s1"
+    }
+
+    fn encode(&self, board: &Board) -> Result<Vec<u8>, PuzzleError> {
+        Ok(encode(&board.to_1d_string()))
+    }
+
+    fn decode_digits(&self, coded: &[u8]) -> Result<Vec<u8>, PuzzleError> {
+        to_digits(&decode(coded))
+    }
+}
+
+fn encode(string: &str) -> Vec<u8> {
     let mut iter = string
         .chars()
         .map(|x| x.to_digit(10).unwrap() as u8)
-        .into_iter()
         .peekable();
 
     let mut ret: Vec<u8> = vec![];
 
-    loop {
-        let mut outnum;
-        let num1 = encode_single(&mut iter);
-        if num1 == None {
-            break;
-        } else {
-            outnum = num1.unwrap() << 4;
-        }
+    while let Some(num1) = encode_single(&mut iter) {
+        let mut outnum = num1 << 4;
 
         let num2 = encode_single(&mut iter);
         if let Some(num2) = num2 {
@@ -61,7 +76,7 @@ fn encode_single<T: Iterator<Item = u8>>(iter: &mut Peekable<T>) -> Option<u8> {
 }
 
 #[allow(dead_code)]
-pub fn trivial_encode(string: &str) -> Vec<u8> {
+fn trivial_encode(string: &str) -> Vec<u8> {
     let nums: Vec<u8> = string
         .chars()
         .map(|x| x.to_digit(10).unwrap() as u8)
@@ -69,25 +84,25 @@ pub fn trivial_encode(string: &str) -> Vec<u8> {
     nums.chunks(2)
         .map({
             |pair| match pair {
-                [x, y] => (x << 4 | y),
-                [x] => (x << 4),
+                [x, y] => x << 4 | y,
+                [x] => x << 4,
                 _ => panic!("Toast"),
             }
         })
         .collect()
 }
 
-pub fn decode(coded: Vec<u8>) -> String {
+fn decode(coded: &[u8]) -> String {
     let mut ret = String::with_capacity(81);
 
     fn push_nibble(ret: &mut String, nibble: u8) {
         match nibble {
-            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 => ret.push_str(&format!("{}", nibble)),
+            0..=9 => ret.push_str(&format!("{}", nibble)),
             x => ret.push_str("0".repeat((x - 8) as usize).as_str()),
         }
     }
 
-    for byte in coded {
+    for &byte in coded {
         let first = (byte & 0xF0) >> 4;
         let second = byte & 0x0F;
 
@@ -0,0 +1,84 @@
+use crate::commands::{is_number, parse_puzzle, ENGINE};
+use crate::solver::board::Board;
+use crate::solver::solve::Engine;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+use std::time::Instant;
+
+const COMMAND_NAME: &str = "bench";
+const PUZZLES: &str = "puzzles";
+const REPEAT: &str = "repeat";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Measures how fast the solver engines are")
+            .arg(
+                Arg::with_name(PUZZLES)
+                    .required(true)
+                    .multiple(true)
+                    .help("The puzzles to solve"),
+            )
+            .arg(
+                Arg::with_name(ENGINE)
+                    .long("engine")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .possible_values(&Engine::NAMES)
+                    .help("The engines to compare, all of them if none are given"),
+            )
+            .arg(
+                Arg::with_name(REPEAT)
+                    .short("n")
+                    .long("repeat")
+                    .takes_value(true)
+                    .validator(is_number(1, u32::MAX))
+                    .default_value("1")
+                    .help("How often to solve every puzzle"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzles = match matches.values_of(PUZZLES) {
+        Some(puzzles) => puzzles
+            .map(parse_puzzle)
+            .collect::<Result<Vec<Board>, _>>()?,
+        _ => return Ok(()),
+    };
+    let engines = match matches.values_of(ENGINE) {
+        Some(names) => names
+            .map(|name| name.parse())
+            .collect::<Result<Vec<Engine>, _>>()?,
+        None => Engine::ALL.to_vec(),
+    };
+    let repeat: u32 = matches.value_of(REPEAT).unwrap_or("1").parse()?;
+
+    for engine in engines {
+        let start = Instant::now();
+        let mut solved = 0;
+        for _ in 0..repeat {
+            for board in &puzzles {
+                // Proving uniqueness is part of the work for a real puzzle
+                if let Ok(Some(_)) = engine.solve(board.clone(), true) {
+                    solved += 1;
+                }
+            }
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        let total = puzzles.len() as f64 * repeat as f64;
+        println!(
+            "{:<10} {:>8} solved in {:>8.3}s ({:.0} puzzles/s)",
+            engine.name(),
+            solved,
+            elapsed,
+            total / elapsed
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::commands::{open_input, parse_puzzle, strip_comment};
+use crate::solver::transform::canonical;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+use std::io::BufRead;
+
+const COMMAND_NAME: &str = "canon";
+const PUZZLES: &str = "puzzles";
+const INPUT: &str = "input";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Prints the minlex form of puzzles, which is the same for all their transformations")
+            .arg(
+                Arg::with_name(PUZZLES)
+                    .required_unless(INPUT)
+                    .multiple(true)
+                    .help("The puzzles or grids to bring into minlex form"),
+            )
+            .arg(
+                Arg::with_name(INPUT)
+                    .short("i")
+                    .long("input")
+                    .takes_value(true)
+                    .help("Reads puzzles from a file with one per line, or from stdin with -"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    for puzzle in matches.values_of(PUZZLES).into_iter().flatten() {
+        print_canonical(puzzle)?;
+    }
+    if let Some(path) = matches.value_of(INPUT) {
+        for line in open_input(path)?.lines() {
+            if let Some(puzzle) = strip_comment(&line?) {
+                print_canonical(puzzle)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_canonical(puzzle: &str) -> Result<(), Box<dyn Error>> {
+    let board = parse_puzzle(puzzle)?;
+    println!("{}", canonical(&board).apply(&board).to_1d_string());
+    Ok(())
+}
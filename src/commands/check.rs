@@ -0,0 +1,323 @@
+use crate::commands::{is_encoded, parse_digits};
+use crate::errors::PuzzleError;
+use crate::solver::analysis::redundant_givens;
+use crate::solver::board::Board;
+use crate::solver::calc::Cell;
+use crate::solver::solve::Engine;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "check";
+const PUZZLE: &str = "puzzle";
+const PROGRESS: &str = "progress";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Checks a puzzle for problems, and a player's progress for mistakes")
+            .arg(
+                Arg::with_name(PUZZLE)
+                    .required(true)
+                    .help("The puzzle to check"),
+            )
+            .arg(
+                Arg::with_name(PROGRESS)
+                    .help("The grid filled in by a player, to compare with the solution"),
+            ),
+    )
+}
+
+fn report(out: &mut String, check: &str, result: Result<(), String>) {
+    let answer = match result {
+        Ok(()) => "yes".to_string(),
+        Err(reason) => format!("no ({})", reason),
+    };
+    out.push_str(&format!("{:<20}{}\n", format!("{}:", check), answer));
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzle = match matches.value_of(PUZZLE) {
+        Some(puzzle) => puzzle,
+        _ => return Ok(()),
+    };
+    let mut out = String::new();
+    let result = check(&mut out, puzzle, matches.value_of(PROGRESS));
+    print!("{}", out);
+    result
+}
+
+// Writes the result of every check into out. Fails with the error of the first check that failed,
+// though a puzzle with redundant givens can still be played, so that only counts without progress.
+fn check(out: &mut String, puzzle: &str, progress: Option<&str>) -> Result<(), Box<dyn Error>> {
+    // Every check of the puzzle needs the ones before it to pass
+    let nums = match parse_digits(puzzle) {
+        Ok(nums) => nums,
+        Err(error) => {
+            report(out, "Valid", Err(error.to_string()));
+            return Err(Box::new(error));
+        }
+    };
+    report(out, "Valid", Ok(()));
+    let board = match Board::from_puzzle(nums.clone()) {
+        Ok(board) => board,
+        Err(error) => {
+            // The offsets of encoded puzzles would point into the decoded puzzle, not the input
+            let error = if is_encoded(puzzle) {
+                error.without_offset()
+            } else {
+                error
+            };
+            report(out, "Contradiction-free", Err(error.to_string()));
+            return Err(Box::new(error));
+        }
+    };
+    report(out, "Contradiction-free", Ok(()));
+    let mut failure = None;
+    let solution = match Engine::Bitboard.solve(board, true) {
+        Ok(Some(solution)) => Some(solution.to_nums()),
+        Ok(None) => {
+            report(out, "Solvable", Err("no solution".to_string()));
+            return Err(Box::new(PuzzleError::Unsolvable));
+        }
+        Err(PuzzleError::Ambiguous) => {
+            failure = Some(PuzzleError::Ambiguous);
+            None
+        }
+        Err(error) => return Err(Box::new(error)),
+    };
+    report(out, "Solvable", Ok(()));
+    match solution {
+        Some(_) => report(out, "Unique", Ok(())),
+        None => report(out, "Unique", Err("more than one solution".to_string())),
+    }
+    if solution.is_some() {
+        let redundant: Vec<String> = redundant_givens(&nums)
+            .iter()
+            .map(|cell| cell.name())
+            .collect();
+        if redundant.is_empty() {
+            report(out, "Minimal", Ok(()));
+        } else {
+            let reason = format!("redundant givens: {}", redundant.join(", "));
+            report(out, "Minimal", Err(reason));
+            if progress.is_none() {
+                failure = Some(PuzzleError::Redundant);
+            }
+        }
+    }
+
+    if let Some(progress) = progress {
+        let progress = parse_digits(progress)?;
+        if !check_progress(out, &nums, &progress, solution.as_deref()) {
+            failure.get_or_insert(PuzzleError::Mistakes);
+        }
+    }
+    match failure {
+        Some(error) => Err(Box::new(error)),
+        None => Ok(()),
+    }
+}
+
+// Compares a player's progress with the givens and the solution. Returns whether it has no
+// mistakes.
+fn check_progress(
+    out: &mut String,
+    givens: &[u8],
+    progress: &[u8],
+    solution: Option<&[u8]>,
+) -> bool {
+    let cell = |i: usize, digit: u8| format!("{}={}", Cell(i as u8).name(), digit);
+    let changed: Vec<String> = (0..81)
+        .filter(|&i| givens[i] != 0 && progress[i] != 0 && progress[i] != givens[i])
+        .map(|i| format!("{} instead of {}", cell(i, progress[i]), givens[i]))
+        .collect();
+    report(
+        out,
+        "Givens kept",
+        if changed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("changed givens: {}", changed.join(", ")))
+        },
+    );
+
+    let conflicts: Vec<String> = (0..81)
+        .filter(|&i| progress[i] != 0)
+        .flat_map(|i| {
+            Cell(i as u8)
+                .peers()
+                .iter()
+                .filter(move |peer| peer.0 as usize > i && progress[peer.0 as usize] == progress[i])
+                .map(move |peer| {
+                    format!(
+                        "{} and {} are both {}",
+                        Cell(i as u8).name(),
+                        peer.name(),
+                        progress[i]
+                    )
+                })
+        })
+        .collect();
+    report(
+        out,
+        "Conflict-free",
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(conflicts.join(", "))
+        },
+    );
+
+    let solution = match solution {
+        Some(solution) => solution,
+        None => {
+            report(
+                out,
+                "Correct",
+                Err("there is no unique solution".to_string()),
+            );
+            return false;
+        }
+    };
+    let filled = progress.iter().filter(|&&x| x != 0).count();
+    // Changed givens are already listed above
+    let wrong: Vec<String> = (0..81)
+        .filter(|&i| givens[i] == 0 && progress[i] != 0 && progress[i] != solution[i])
+        .map(|i| cell(i, progress[i]))
+        .collect();
+    if wrong.is_empty() {
+        report(out, "Correct", Ok(()));
+    } else {
+        report(
+            out,
+            "Correct",
+            Err(format!(
+                "{} of {} filled cells are wrong: {}",
+                wrong.len(),
+                filled,
+                wrong.join(", ")
+            )),
+        );
+    }
+
+    let correct = changed.is_empty() && conflicts.is_empty() && wrong.is_empty();
+    if correct && filled == 81 {
+        out.push_str("The puzzle is solved\n");
+    }
+    correct
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "000700008200539100005001000000600002036240000400058006002005000607000023803006001";
+    const SOLUTION: &str =
+        "391762548284539167765481239578693412136247895429158376912375684657814923843926751";
+
+    // The output of the checks and the exit code
+    fn run(puzzle: &str, progress: Option<&str>) -> (String, i32) {
+        let mut out = String::new();
+        let code = match check(&mut out, puzzle, progress) {
+            Ok(()) => 0,
+            Err(error) => error
+                .downcast_ref::<PuzzleError>()
+                .map_or(1, PuzzleError::exit_code),
+        };
+        (out, code)
+    }
+
+    // The puzzle with some cells filled in, given as (index, digit)
+    fn progress(cells: &[(usize, u8)]) -> String {
+        let mut ret: Vec<u8> = PUZZLE.bytes().collect();
+        for &(i, digit) in cells {
+            ret[i] = b'0' + digit;
+        }
+        String::from_utf8(ret).unwrap()
+    }
+
+    fn progress_lines(out: &str) -> Vec<&str> {
+        out.lines().skip(5).collect()
+    }
+
+    // Encoded puzzles can't be checked for contradictions while decoding them
+    #[test]
+    fn encoded_contradiction() {
+        let (out, code) = run("s1.Ef______-g", None);
+        assert_eq!(
+            out,
+            "Valid:              yes\n\
+             Contradiction-free: no (digit 1 at r1c2 conflicts with r1c1)\n"
+        );
+        assert_eq!(code, 3);
+    }
+
+    #[test]
+    fn redundant_givens_fail_without_progress() {
+        let (out, code) = run(PUZZLE, None);
+        assert!(out.contains("Minimal:            no (redundant givens: r2c1,"));
+        assert_eq!(code, 7);
+    }
+
+    #[test]
+    fn correct_progress() {
+        let (out, code) = run(PUZZLE, Some(&progress(&[(0, 3), (1, 9)])));
+        assert_eq!(
+            progress_lines(&out),
+            vec![
+                "Givens kept:        yes",
+                "Conflict-free:      yes",
+                "Correct:            yes",
+            ]
+        );
+        assert_eq!(code, 0);
+    }
+
+    #[test]
+    fn solved_progress() {
+        let (out, code) = run(PUZZLE, Some(SOLUTION));
+        assert_eq!(progress_lines(&out).last(), Some(&"The puzzle is solved"));
+        assert_eq!(code, 0);
+    }
+
+    #[test]
+    fn wrong_progress() {
+        let (out, code) = run(PUZZLE, Some(&progress(&[(0, 9), (1, 9)])));
+        assert_eq!(
+            progress_lines(&out),
+            vec![
+                "Givens kept:        yes",
+                "Conflict-free:      no (r1c1 and r1c2 are both 9)",
+                "Correct:            no (1 of 31 filled cells are wrong: r1c1=9)",
+            ]
+        );
+        assert_eq!(code, 8);
+    }
+
+    // A changed given is wrong as well, but only reported as changed
+    #[test]
+    fn changed_given() {
+        let (out, code) = run(PUZZLE, Some(&progress(&[(3, 4)])));
+        assert_eq!(
+            progress_lines(&out),
+            vec![
+                "Givens kept:        no (changed givens: r1c4=4 instead of 7)",
+                "Conflict-free:      yes",
+                "Correct:            yes",
+            ]
+        );
+        assert_eq!(code, 8);
+    }
+
+    #[test]
+    fn progress_of_ambiguous_puzzle() {
+        let (out, code) = run(&"0".repeat(81), Some(SOLUTION));
+        assert!(out.ends_with("Correct:            no (there is no unique solution)\n"));
+        assert_eq!(code, 6);
+    }
+}
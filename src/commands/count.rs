@@ -0,0 +1,54 @@
+use crate::commands::{engine, engine_arg, is_number, parse_puzzle};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "count";
+const PUZZLES: &str = "puzzles";
+const LIMIT: &str = "limit";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Counts the solutions of puzzles")
+            .arg(
+                Arg::with_name(PUZZLES)
+                    .required(true)
+                    .multiple(true)
+                    .help("The puzzles to count the solutions of"),
+            )
+            .arg(
+                Arg::with_name(LIMIT)
+                    .short("l")
+                    .long("limit")
+                    .takes_value(true)
+                    .validator(is_number(1, u32::MAX))
+                    .default_value("1000")
+                    .help("Stop counting after this many solutions"),
+            )
+            .arg(engine_arg()),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzles = match matches.values_of(PUZZLES) {
+        Some(puzzles) => puzzles,
+        _ => return Ok(()),
+    };
+    let limit: usize = matches.value_of(LIMIT).unwrap_or("1000").parse()?;
+    let engine = engine(matches)?;
+    for puzzle in puzzles {
+        let board = parse_puzzle(puzzle)?;
+        // Looking for one more than the limit tells us whether it was reached
+        let count = engine.solutions(board).take(limit + 1).count();
+        if count > limit {
+            println!("{}\tmore than {}", puzzle, limit);
+        } else {
+            println!("{}\t{}", puzzle, count);
+        }
+    }
+    Ok(())
+}
@@ -1,10 +1,11 @@
-use crate::codex::simple::encode;
+use crate::codex;
 use crate::commands::parse_puzzle;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use std::error::Error;
 
 const COMMAND_NAME: &str = "encode";
 const PUZZLES: &str = "puzzles";
+const CODEC: &str = "codec";
 
 pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
     app.subcommand(
@@ -15,6 +16,15 @@ pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
                     .required(true)
                     .multiple(true)
                     .help("The puzzles to solve"),
+            )
+            .arg(
+                Arg::with_name(CODEC)
+                    .short("c")
+                    .long("codec")
+                    .takes_value(true)
+                    .possible_values(&codex::names())
+                    .default_value("simple")
+                    .help("The format to encode the puzzles in"),
             ),
     )
 }
@@ -24,13 +34,11 @@ pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
         Some(matches) => matches,
         _ => return Ok(()),
     };
+    let codec = codex::by_name(matches.value_of(CODEC).unwrap_or("simple"))?;
     if let Some(puzzles) = matches.values_of(PUZZLES) {
         for puzzle in puzzles {
             let board = parse_puzzle(puzzle)?;
-            println!(
-                "{}",
-                base64::encode_config(&encode(&board.to_1d_string()), base64::URL_SAFE_NO_PAD)
-            );
+            println!("{}", codex::encode(&board, codec)?);
         }
     }
     Ok(())
@@ -0,0 +1,90 @@
+use crate::commands::{is_number, parse_puzzle};
+use crate::errors::PuzzleError;
+use crate::solver::analysis::{differing_cells, disambiguate};
+use crate::solver::solve::Engine;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "fix";
+const PUZZLE: &str = "puzzle";
+const SOLUTION: &str = "solution";
+const LIMIT: &str = "limit";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Adds the fewest givens that make an ambiguous puzzle unique")
+            .arg(
+                Arg::with_name(PUZZLE)
+                    .required(true)
+                    .help("The puzzle to fix"),
+            )
+            .arg(
+                Arg::with_name(SOLUTION)
+                    .short("s")
+                    .long("solution")
+                    .takes_value(true)
+                    .validator(is_number(1, u32::MAX))
+                    .default_value("1")
+                    .help("Which of the solutions to keep, in the order the solver finds them"),
+            )
+            .arg(
+                Arg::with_name(LIMIT)
+                    .short("l")
+                    .long("limit")
+                    .takes_value(true)
+                    .validator(is_number(1, 81))
+                    .default_value("4")
+                    .help("Give up if more givens than this are needed"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzle = match matches.value_of(PUZZLE) {
+        Some(puzzle) => puzzle,
+        _ => return Ok(()),
+    };
+    let index: usize = matches.value_of(SOLUTION).unwrap_or("1").parse()?;
+    let limit: usize = matches.value_of(LIMIT).unwrap_or("4").parse()?;
+    let board = parse_puzzle(puzzle)?;
+    let nums = board.to_nums();
+    let solution = match Engine::Bitboard.solutions(board).nth(index - 1) {
+        Some(solution) => solution.to_nums(),
+        None if index == 1 => return Err(Box::new(PuzzleError::Unsolvable)),
+        None => return Err(format!("The puzzle has fewer than {} solutions", index).into()),
+    };
+
+    let differing = differing_cells(&nums, &solution);
+    if differing.is_empty() {
+        println!("The puzzle already has a unique solution");
+        return Ok(());
+    }
+    let names: Vec<String> = differing.iter().map(|cell| cell.name()).collect();
+    println!("Solutions differ in: {}", names.join(", "));
+
+    let mut clues = match disambiguate(&nums, &solution, limit) {
+        Some(clues) => clues,
+        None => return Err(format!("More than {} givens are needed", limit).into()),
+    };
+    clues.sort();
+    let mut fixed = nums;
+    let added: Vec<String> = clues
+        .iter()
+        .map(|cell| {
+            let i = cell.0 as usize;
+            fixed[i] = solution[i];
+            format!("{}={}", cell.name(), solution[i])
+        })
+        .collect();
+    println!("Added givens: {}", added.join(", "));
+    println!(
+        "{}",
+        fixed.iter().map(|x| x.to_string()).collect::<String>()
+    );
+    Ok(())
+}
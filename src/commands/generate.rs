@@ -0,0 +1,83 @@
+use crate::codex::{self, DEFAULT_CODEC};
+use crate::commands::is_number;
+use crate::solver::generate::{generate, Target};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "generate";
+const DIFFICULTY: &str = "difficulty";
+const CLUES: &str = "clues";
+const COUNT: &str = "count";
+const ENCODE: &str = "encode";
+const PRETTY: &str = "pretty";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Generates new puzzles")
+            .arg(
+                Arg::with_name(DIFFICULTY)
+                    .short("d")
+                    .long("difficulty")
+                    .takes_value(true)
+                    .possible_values(&["easy", "medium", "hard", "expert"])
+                    .default_value("medium")
+                    .help("The difficulty of the generated puzzles"),
+            )
+            .arg(
+                Arg::with_name(CLUES)
+                    .short("c")
+                    .long("clues")
+                    .takes_value(true)
+                    .validator(is_number(17, 81))
+                    .help("Remove clues until only this many are left (overrides the difficulty)"),
+            )
+            .arg(
+                Arg::with_name(COUNT)
+                    .short("n")
+                    .long("count")
+                    .takes_value(true)
+                    .validator(is_number(1, u32::MAX))
+                    .default_value("1")
+                    .help("The number of puzzles to generate"),
+            )
+            .arg(
+                Arg::with_name(ENCODE)
+                    .short("e")
+                    .long("encode")
+                    .conflicts_with(PRETTY)
+                    .help("Outputs the puzzles in the compressed base64 format"),
+            )
+            .arg(
+                Arg::with_name(PRETTY)
+                    .short("p")
+                    .long("pretty-print")
+                    .help("Displays the generated puzzles nicely"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let target = match matches.value_of(CLUES) {
+        Some(clues) => Target::Clues(clues.parse()?),
+        None => Target::Difficulty(matches.value_of(DIFFICULTY).unwrap_or("medium").parse()?),
+    };
+    let count: u32 = matches.value_of(COUNT).unwrap_or("1").parse()?;
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..count {
+        let board = generate(&mut rng, target);
+        if matches.is_present(ENCODE) {
+            println!("{}", codex::encode(&board, DEFAULT_CODEC)?);
+        } else if matches.is_present(PRETTY) {
+            println!("{}", board);
+        } else {
+            println!("{}", board.to_1d_string());
+        }
+    }
+    Ok(())
+}
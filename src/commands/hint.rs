@@ -0,0 +1,77 @@
+use crate::commands::{is_number, parse_puzzle};
+use crate::solver::logic::{format_regions, next_step, LogicOptions, Step};
+use crate::solver::solve::solve;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "hint";
+const PUZZLE: &str = "puzzle";
+const LEVEL: &str = "level";
+const UNAMBIGUOUS: &str = "unambiguous";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Gives a hint for the next logical step in a (partially filled) puzzle")
+            .arg(
+                Arg::with_name(PUZZLE)
+                    .required(true)
+                    .help("The puzzle, including the digits filled in so far"),
+            )
+            .arg(
+                Arg::with_name(LEVEL)
+                    .short("l")
+                    .long("level")
+                    .takes_value(true)
+                    .validator(is_number(1, 3))
+                    .default_value("1")
+                    .help("How much to reveal: 1 = the technique, 2 = where to look, 3 = the full step"),
+            )
+            .arg(
+                Arg::with_name(UNAMBIGUOUS)
+                    .short("u")
+                    .long("unambiguous")
+                    .help("Test the puzzle for ambiguity (allows uniqueness techniques)"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let board = match matches.value_of(PUZZLE) {
+        Some(puzzle) => parse_puzzle(puzzle)?,
+        _ => return Ok(()),
+    };
+    let level: u32 = matches.value_of(LEVEL).unwrap_or("1").parse()?;
+    let ambiguity = matches.is_present(UNAMBIGUOUS);
+    if ambiguity {
+        solve(board.clone(), true)?;
+    }
+    if board.is_solved() {
+        println!("The puzzle is already solved");
+        return Ok(());
+    }
+    let options = LogicOptions {
+        assume_unique: ambiguity,
+    };
+    match next_step(&board, &options) {
+        Some(step) => println!("{}", format_hint(&step, level)),
+        None => println!("No known technique applies anymore, the rest requires guessing"),
+    }
+    Ok(())
+}
+
+fn format_hint(step: &Step, level: u32) -> String {
+    match level {
+        1 => format!("Technique: {}", step.technique),
+        2 => format!(
+            "Technique: {} in {}",
+            step.technique,
+            format_regions(&step.pattern.regions())
+        ),
+        _ => step.to_string(),
+    }
+}
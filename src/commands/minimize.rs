@@ -0,0 +1,58 @@
+use crate::commands::parse_puzzle;
+use crate::errors::PuzzleError;
+use crate::solver::analysis::{minimize, Symmetry};
+use crate::solver::solve::Engine;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "minimize";
+const PUZZLES: &str = "puzzles";
+const SYMMETRY: &str = "symmetry";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Removes givens from puzzles until every remaining one is needed")
+            .arg(
+                Arg::with_name(PUZZLES)
+                    .required(true)
+                    .multiple(true)
+                    .help("The puzzles to minimize"),
+            )
+            .arg(
+                Arg::with_name(SYMMETRY)
+                    .short("s")
+                    .long("symmetry")
+                    .takes_value(true)
+                    .possible_values(&Symmetry::NAMES)
+                    .default_value("none")
+                    .help("Only removes givens together with their mirror images"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzles = match matches.values_of(PUZZLES) {
+        Some(puzzles) => puzzles,
+        _ => return Ok(()),
+    };
+    let symmetry: Symmetry = matches.value_of(SYMMETRY).unwrap_or("none").parse()?;
+    for puzzle in puzzles {
+        let board = parse_puzzle(puzzle)?;
+        let nums = board.to_nums();
+        // Removing givens can't make an ambiguous puzzle unique
+        if Engine::Bitboard.solve(board, true)?.is_none() {
+            return Err(Box::new(PuzzleError::Unsolvable));
+        }
+        let minimal = minimize(&nums, symmetry);
+        println!(
+            "{}",
+            minimal.iter().map(|x| x.to_string()).collect::<String>()
+        );
+    }
+    Ok(())
+}
@@ -1,26 +1,103 @@
-use crate::codex::simple::decode;
-use crate::errors::InvalidPuzzleError;
+use crate::codex;
+use crate::errors::PuzzleError;
 use crate::solver::board::Board;
+use crate::solver::solve::Engine;
+use clap::{Arg, ArgMatches};
 use std::error::Error;
+use std::fs::File;
+use std::io::{stdin, BufRead, BufReader};
 
-pub mod solve;
-pub mod encode;
+pub mod bench;
+pub mod canon;
+pub mod check;
+pub mod count;
 pub mod decode;
+pub mod encode;
+pub mod fix;
+pub mod generate;
+pub mod hint;
+pub mod minimize;
+pub mod play;
+pub mod rate;
+pub mod same;
+pub mod solve;
+pub mod transform;
+
+pub fn parse_puzzle(puzzle: &str) -> Result<Board, PuzzleError> {
+    if is_encoded(puzzle) {
+        codex::decode(puzzle)
+    } else {
+        Board::from_puzzle(parse_digits(puzzle)?)
+    }
+}
+
+pub fn is_encoded(puzzle: &str) -> bool {
+    puzzle.len() < 81
+}
+
+// Reads the digits of a puzzle (0 for empty cells), without checking that they fit together
+pub fn parse_digits(puzzle: &str) -> Result<Vec<u8>, PuzzleError> {
+    if is_encoded(puzzle) {
+        return codex::decode_digits(puzzle);
+    }
+
+    let cells = puzzle.chars().count();
+    if cells != 81 {
+        return Err(PuzzleError::Invalid {
+            offset: Some(81.min(cells)),
+            reason: format!("Expected 81 cells, found {}", cells),
+        });
+    }
+    Ok(to_digits(puzzle))
+}
+
+// Anything that isn't a digit is an empty cell
+fn to_digits(puzzle: &str) -> Vec<u8> {
+    puzzle
+        .chars()
+        .map(|x| x.to_digit(10).map(|x| x as u8).unwrap_or(0))
+        .collect()
+}
+
+// Validator for clap arguments that have to be a number in the given range
+pub fn is_number(min: u32, max: u32) -> impl Fn(String) -> Result<(), String> {
+    move |val: String| match val.parse::<u32>() {
+        Ok(num) if num >= min && num <= max => Ok(()),
+        _ => Err(format!("Must be a number between {} and {}", min, max)),
+    }
+}
+
+pub const ENGINE: &str = "engine";
+
+// The option to pick the algorithm that searches for solutions
+pub fn engine_arg<'a>() -> Arg<'a, 'a> {
+    Arg::with_name(ENGINE)
+        .long("engine")
+        .takes_value(true)
+        .possible_values(&Engine::NAMES)
+        .default_value("backtrack")
+        .help("The algorithm used to search for solutions")
+}
+
+pub fn engine(matches: &ArgMatches) -> Result<Engine, String> {
+    matches.value_of(ENGINE).unwrap_or("backtrack").parse()
+}
+
+// Opens a file to read puzzles from, or stdin for -
+pub fn open_input(path: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    if path == "-" {
+        Ok(Box::new(BufReader::new(stdin())))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
 
-pub fn parse_puzzle(puzzle: &str) -> Result<Board, Box<dyn Error>> {
-    let decoded_puzzle = if puzzle.len() < 81 {
-        decode(base64::decode_config(puzzle, base64::URL_SAFE_NO_PAD)?)
+// The puzzle on a line of an input, without any comment. None if nothing is left.
+pub fn strip_comment(line: &str) -> Option<&str> {
+    let puzzle = line.split('#').next().unwrap_or("").trim();
+    if puzzle.is_empty() {
+        None
     } else {
-        puzzle.to_string()
-    };
-
-    if decoded_puzzle.len() == 81 {
-        let ret: Vec<u8> = decoded_puzzle
-            .chars()
-            .map(|x| x.to_digit(10).map(|x| x as u8).unwrap_or(0))
-            .collect();
-        let board = Board::from_puzzle(ret)?;
-        return Ok(board);
+        Some(puzzle)
     }
-    Err(Box::new(InvalidPuzzleError {}))
 }
@@ -0,0 +1,51 @@
+use crate::commands::parse_puzzle;
+use crate::errors::PuzzleError;
+use crate::game::Game;
+use crate::solver::generate::{generate, Target};
+use crate::solver::solve::solve;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "play";
+const PUZZLE: &str = "puzzle";
+const DIFFICULTY: &str = "difficulty";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Play a game of sudoku in the terminal")
+            .arg(
+                Arg::with_name(PUZZLE)
+                    .conflicts_with(DIFFICULTY)
+                    .help("The puzzle to play, a new one is generated if none is given"),
+            )
+            .arg(
+                Arg::with_name(DIFFICULTY)
+                    .short("d")
+                    .long("difficulty")
+                    .takes_value(true)
+                    .possible_values(&["easy", "medium", "hard", "expert"])
+                    .help("The difficulty of the generated puzzle"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzle = match matches.value_of(PUZZLE) {
+        Some(puzzle) => parse_puzzle(puzzle)?,
+        None => {
+            let difficulty = matches.value_of(DIFFICULTY).unwrap_or("medium").parse()?;
+            generate(&mut rand::thread_rng(), Target::Difficulty(difficulty))
+        }
+    };
+    // The game compares the grid with the solution, so there must only be one
+    let solution = match solve(puzzle.clone(), true)? {
+        Some(solution) => solution,
+        None => return Err(Box::new(PuzzleError::Unsolvable)),
+    };
+    Game::new(&puzzle, &solution).run()
+}
@@ -0,0 +1,70 @@
+use crate::commands::parse_puzzle;
+use crate::solver::logic::LogicOptions;
+use crate::solver::rating::{rate, Rating};
+use crate::solver::solve::solve;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "rate";
+const UNAMBIGUOUS: &str = "unambiguous";
+const PUZZLES: &str = "puzzles";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Rates the difficulty of puzzles on the Sudoku Explainer scale")
+            .arg(
+                Arg::with_name(PUZZLES)
+                    .required(true)
+                    .multiple(true)
+                    .help("The puzzles to rate"),
+            )
+            .arg(
+                Arg::with_name(UNAMBIGUOUS)
+                    .short("u")
+                    .long("unambiguous")
+                    .help("Test the puzzle for ambiguity (allows uniqueness techniques)"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzles = match matches.values_of(PUZZLES) {
+        Some(puzzles) => puzzles,
+        _ => return Ok(()),
+    };
+    let ambiguity = matches.is_present(UNAMBIGUOUS);
+    for puzzle in puzzles {
+        let board = parse_puzzle(puzzle)?;
+        if ambiguity {
+            solve(board.clone(), true)?;
+        }
+        let options = LogicOptions {
+            assume_unique: ambiguity,
+        };
+        let rating = rate(board, &options)?;
+        println!("{}\t{}", puzzle, format_rating(&rating));
+    }
+    Ok(())
+}
+
+fn format_rating(rating: &Rating) -> String {
+    let counts: Vec<String> = rating
+        .counts
+        .iter()
+        .map(|(technique, count)| format!("{} x{}", technique, count))
+        .collect();
+    if rating.needs_guessing {
+        // The steps before getting stuck say nothing about how hard the rest is
+        return format!("?\tNeeds guessing\t{}", counts.join(", "));
+    }
+    let hardest = rating
+        .hardest
+        .map(|technique| technique.name())
+        .unwrap_or("Nothing");
+    format!("{:.1}\t{}\t{}", rating.rating, hardest, counts.join(", "))
+}
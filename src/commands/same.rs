@@ -0,0 +1,46 @@
+use crate::commands::parse_puzzle;
+use crate::solver::transform::canonical;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::error::Error;
+
+const COMMAND_NAME: &str = "same";
+const FIRST: &str = "first";
+const SECOND: &str = "second";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Checks whether two puzzles are transformations of each other")
+            .arg(
+                Arg::with_name(FIRST)
+                    .required(true)
+                    .help("The puzzle or grid to transform"),
+            )
+            .arg(
+                Arg::with_name(SECOND)
+                    .required(true)
+                    .help("The puzzle or grid it should be transformed into"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let (first, second) = match (matches.value_of(FIRST), matches.value_of(SECOND)) {
+        (Some(first), Some(second)) => (parse_puzzle(first)?, parse_puzzle(second)?),
+        _ => return Ok(()),
+    };
+    // Both lead to the same minlex form, so going there from the first and back from there to
+    // the second maps one onto the other
+    let to_first = canonical(&first);
+    let to_second = canonical(&second);
+    if to_first.apply(&first).to_1d_string() != to_second.apply(&second).to_1d_string() {
+        println!("The puzzles are not transformations of each other");
+        return Ok(());
+    }
+    println!("{}", to_first.then(&to_second.inverse()));
+    Ok(())
+}
@@ -1,13 +1,24 @@
-use crate::commands::parse_puzzle;
-use crate::errors::UnsolvableError;
-use crate::solver::solve::solve;
+use crate::commands::{engine, engine_arg, open_input, parse_puzzle, strip_comment};
+use crate::errors::PuzzleError;
+use crate::solver::logic::{solve_logically, LogicOptions};
+use crate::solver::solve::Engine;
 use clap::{App, Arg, ArgMatches, SubCommand};
+use rayon::prelude::*;
 use std::error::Error;
+use std::fmt::Write;
+use std::io::{self, stdout, BufRead, Write as IoWrite};
+use std::time::Instant;
 
 const COMMAND_NAME: &str = "solve";
 const UNAMBIGUOUS: &str = "unambiguous";
 const PUZZLES: &str = "puzzles";
 const PRETTY: &str = "pretty";
+const EXPLAIN: &str = "explain";
+const INPUT: &str = "input";
+const STDIN: &str = "-";
+
+// How many puzzles of an input are solved in parallel before their results are printed
+const BATCH_SIZE: usize = 4096;
 
 pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
     app.subcommand(
@@ -15,47 +26,201 @@ pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
             .about("Solves a given puzzle")
             .arg(
                 Arg::with_name(PUZZLES)
-                    .required(true)
+                    .required_unless(INPUT)
                     .multiple(true)
-                    .help("The puzzles to solve"),
+                    .help("The puzzles to solve, or - to read them from stdin"),
+            )
+            .arg(
+                Arg::with_name(INPUT)
+                    .short("i")
+                    .long("input")
+                    .takes_value(true)
+                    .help("Reads puzzles from a file with one per line, or from stdin with -"),
             )
             .arg(
                 Arg::with_name(UNAMBIGUOUS)
                     .short("u")
                     .long("unambiguous")
-                    .help("Test the puzzle for ambiguity"),
+                    .help(
+                    "Test the puzzle for ambiguity (allows uniqueness techniques in explanations)",
+                ),
             )
             .arg(
                 Arg::with_name(PRETTY)
                     .short("p")
                     .long("pretty-print")
                     .help("Displays the solved puzzles nicely"),
-            ),
+            )
+            .arg(
+                Arg::with_name(EXPLAIN)
+                    .short("e")
+                    .long("explain")
+                    .help("Explains the logical steps that lead to the solution"),
+            )
+            .arg(engine_arg()),
     )
 }
 
+struct Settings {
+    engine: Engine,
+    ambiguity: bool,
+    explain: bool,
+    pretty: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Outcome {
+    Solved,
+    Unsolvable,
+    Ambiguous,
+    Invalid,
+}
+
+impl Outcome {
+    fn of(error: &(dyn Error + 'static)) -> Outcome {
+        match error.downcast_ref::<PuzzleError>() {
+            Some(PuzzleError::Ambiguous) => Outcome::Ambiguous,
+            Some(PuzzleError::Invalid { .. }) => Outcome::Invalid,
+            Some(_) => Outcome::Unsolvable,
+            None => Outcome::Invalid,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    solved: usize,
+    unsolvable: usize,
+    ambiguous: usize,
+    invalid: usize,
+}
+
+impl Summary {
+    fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Solved => self.solved += 1,
+            Outcome::Unsolvable => self.unsolvable += 1,
+            Outcome::Ambiguous => self.ambiguous += 1,
+            Outcome::Invalid => self.invalid += 1,
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.solved + self.unsolvable + self.ambiguous + self.invalid
+    }
+}
+
 pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
     let matches = match matches.subcommand_matches(COMMAND_NAME) {
         Some(matches) => matches,
         _ => return Ok(()),
     };
-    let puzzles = match matches.values_of(PUZZLES) {
-        Some(puzzles) => puzzles,
-        _ => return Ok(()),
+    let settings = Settings {
+        engine: engine(matches)?,
+        ambiguity: matches.is_present(UNAMBIGUOUS),
+        explain: matches.is_present(EXPLAIN),
+        pretty: matches.is_present(PRETTY),
     };
-    let ambiguity = matches.is_present(UNAMBIGUOUS);
-    for puzzle in puzzles {
-        let board = parse_puzzle(puzzle)?;
-        let ret = solve(board, ambiguity)?;
-        if let Some(board) = ret {
-            if matches.is_present(PRETTY) {
-                println!("{}", board);
-            } else {
-                println!("{}", board.to_1d_string());
+    let mut puzzles: Vec<String> = matches
+        .values_of(PUZZLES)
+        .map(|puzzles| puzzles.map(String::from).collect())
+        .unwrap_or_default();
+    // A puzzle of "-" reads from stdin, like "--input -"
+    let mut input = matches.value_of(INPUT);
+    if puzzles.iter().any(|puzzle| puzzle == STDIN) {
+        if input.is_some_and(|path| path != STDIN) {
+            return Err("Puzzles can't be read from stdin and an input file at once".into());
+        }
+        puzzles.retain(|puzzle| puzzle != STDIN);
+        input = Some(STDIN);
+    }
+
+    let input = match input {
+        Some(path) => open_input(path)?,
+        None => {
+            // Without an input file, the first broken puzzle ends the command
+            for puzzle in puzzles {
+                print!("{}", solve_puzzle(&puzzle, &settings)?);
             }
-        } else {
-            return Err(Box::new(UnsolvableError {}));
+            return Ok(());
+        }
+    };
+
+    let start = Instant::now();
+    let mut summary = Summary::default();
+    solve_batch(&puzzles, &settings, &mut summary)?;
+    let mut lines = input.lines();
+    loop {
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        for line in lines.by_ref() {
+            if let Some(puzzle) = strip_comment(&line?) {
+                batch.push(puzzle.to_string());
+            }
+            if batch.len() == BATCH_SIZE {
+                break;
+            }
+        }
+        if batch.is_empty() {
+            break;
         }
+        solve_batch(&batch, &settings, &mut summary)?;
     }
+    let elapsed = start.elapsed().as_secs_f64();
+    eprintln!(
+        "{} solved, {} unsolvable, {} ambiguous, {} invalid in {:.3}s ({:.0} puzzles/s)",
+        summary.solved,
+        summary.unsolvable,
+        summary.ambiguous,
+        summary.invalid,
+        elapsed,
+        summary.total() as f64 / elapsed
+    );
     Ok(())
 }
+
+// Solves the puzzles on all cores, but prints the results in their original order. Puzzles that
+// fail get their error printed in place of the solution.
+fn solve_batch(puzzles: &[String], settings: &Settings, summary: &mut Summary) -> io::Result<()> {
+    let results: Vec<(String, Outcome)> = puzzles
+        .par_iter()
+        .map(|puzzle| match solve_puzzle(puzzle, settings) {
+            Ok(output) => (output, Outcome::Solved),
+            Err(error) => (format!("{}\n", error), Outcome::of(&*error)),
+        })
+        .collect();
+    let mut out = stdout().lock();
+    for (output, outcome) in results {
+        out.write_all(output.as_bytes())?;
+        summary.add(outcome);
+    }
+    Ok(())
+}
+
+// Everything printed for a single puzzle: the explanation if requested, and the solution
+fn solve_puzzle(puzzle: &str, settings: &Settings) -> Result<String, Box<dyn Error>> {
+    let board = parse_puzzle(puzzle)?;
+    let ret = settings.engine.solve(board.clone(), settings.ambiguity)?;
+    let mut output = String::new();
+    if settings.explain {
+        // Once the puzzle is proven to be unique, we can also use that for the explanation
+        let options = LogicOptions {
+            assume_unique: settings.ambiguity,
+        };
+        let explanation = solve_logically(board, &options)?;
+        for (i, step) in explanation.steps.iter().enumerate() {
+            writeln!(output, "{:>3}. {}", i + 1, step)?;
+        }
+        if !explanation.board.is_solved() {
+            writeln!(
+                output,
+                "No known technique applies anymore, the rest requires guessing"
+            )?;
+        }
+    }
+    match ret {
+        Some(board) if settings.pretty => writeln!(output, "{}", board)?,
+        Some(board) => writeln!(output, "{}", board.to_1d_string())?,
+        None => return Err(Box::new(PuzzleError::Unsolvable)),
+    }
+    Ok(output)
+}
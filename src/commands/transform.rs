@@ -0,0 +1,83 @@
+use crate::commands::{is_number, parse_puzzle};
+use crate::solver::transform::Transform;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::error::Error;
+
+const COMMAND_NAME: &str = "transform";
+const PUZZLES: &str = "puzzles";
+const TRANSFORM: &str = "transform";
+const SEED: &str = "seed";
+const COUNT: &str = "count";
+
+pub fn register_command<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app.subcommand(
+        SubCommand::with_name(COMMAND_NAME)
+            .about("Turns puzzles into different looking ones that are solved the same way")
+            .arg(
+                Arg::with_name(PUZZLES)
+                    .required(true)
+                    .multiple(true)
+                    .help("The puzzles to transform"),
+            )
+            .arg(
+                Arg::with_name(TRANSFORM)
+                    .short("t")
+                    .long("transform")
+                    .takes_value(true)
+                    .validator(|spec| spec.parse::<Transform>().map(|_| ()))
+                    .help("The steps to apply, like \"rotate,digits:1-2,rows:1-3\" (random if not given)"),
+            )
+            .arg(
+                Arg::with_name(SEED)
+                    .short("s")
+                    .long("seed")
+                    .takes_value(true)
+                    .conflicts_with(TRANSFORM)
+                    .validator(is_number(0, u32::MAX))
+                    .help("Makes the random transformations repeatable"),
+            )
+            .arg(
+                Arg::with_name(COUNT)
+                    .short("n")
+                    .long("count")
+                    .takes_value(true)
+                    .conflicts_with(TRANSFORM)
+                    .validator(is_number(1, u32::MAX))
+                    .help("The number of random transformations of each puzzle (1 by default)"),
+            ),
+    )
+}
+
+pub fn execute(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let matches = match matches.subcommand_matches(COMMAND_NAME) {
+        Some(matches) => matches,
+        _ => return Ok(()),
+    };
+    let puzzles = match matches.values_of(PUZZLES) {
+        Some(puzzles) => puzzles,
+        _ => return Ok(()),
+    };
+    let mut rng = match matches.value_of(SEED) {
+        Some(seed) => StdRng::seed_from_u64(seed.parse()?),
+        None => StdRng::from_entropy(),
+    };
+    let count: u32 = matches.value_of(COUNT).unwrap_or("1").parse()?;
+    for puzzle in puzzles {
+        let board = parse_puzzle(puzzle)?;
+        match matches.value_of(TRANSFORM) {
+            Some(spec) => {
+                let transform: Transform = spec.parse()?;
+                println!("{}", transform.apply(&board).to_1d_string());
+            }
+            None => {
+                for _ in 0..count {
+                    let transform = Transform::random(&mut rng);
+                    println!("{}", transform.apply(&board).to_1d_string());
+                }
+            }
+        }
+    }
+    Ok(())
+}
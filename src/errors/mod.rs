@@ -31,38 +31,126 @@ impl fmt::Display for ContradicoryAssignmentError {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct UnsolvableError {}
+// Everything that can be wrong with a puzzle. Offsets are positions in the 81 character form of
+// the puzzle, if it was given in that form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    // The input can't be read as a puzzle at all
+    Invalid {
+        offset: Option<usize>,
+        reason: String,
+    },
+    // A given has the same digit as a given in one of its houses
+    Conflict {
+        cell: Cell,
+        digit: u8,
+        peer: Cell,
+        offset: Option<usize>,
+    },
+    // A given takes the last possible digit from a cell in one of its houses
+    NoCandidates {
+        cell: Cell,
+        digit: u8,
+        peer: Cell,
+        offset: Option<usize>,
+    },
+    Unsolvable,
+    Ambiguous,
+    // Some givens could be removed without making the puzzle ambiguous
+    Redundant,
+    // A player's progress doesn't agree with the puzzle or its solution
+    Mistakes,
+}
 
-impl Error for UnsolvableError {}
+impl PuzzleError {
+    // The exit code of the program, if the error ends it
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            PuzzleError::Invalid { .. } => 2,
+            PuzzleError::Conflict { .. } => 3,
+            PuzzleError::NoCandidates { .. } => 4,
+            PuzzleError::Unsolvable => 5,
+            PuzzleError::Ambiguous => 6,
+            PuzzleError::Redundant => 7,
+            PuzzleError::Mistakes => 8,
+        }
+    }
 
-impl fmt::Display for UnsolvableError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Sudoku cannot be solved")?;
-        Ok(())
+    // Drops the offset, for puzzles that weren't given in the 81 character form
+    pub fn without_offset(self) -> Self {
+        match self {
+            PuzzleError::Invalid { reason, .. } => PuzzleError::Invalid {
+                offset: None,
+                reason,
+            },
+            PuzzleError::Conflict {
+                cell, digit, peer, ..
+            } => PuzzleError::Conflict {
+                cell,
+                digit,
+                peer,
+                offset: None,
+            },
+            PuzzleError::NoCandidates {
+                cell, digit, peer, ..
+            } => PuzzleError::NoCandidates {
+                cell,
+                digit,
+                peer,
+                offset: None,
+            },
+            other => other,
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct InvalidPuzzleError {}
-
-impl Error for InvalidPuzzleError {}
+impl Error for PuzzleError {}
 
-impl fmt::Display for InvalidPuzzleError {
+impl fmt::Display for PuzzleError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "The given string could not be read as a valid puzzle")?;
-        Ok(())
+        match self {
+            PuzzleError::Invalid {
+                offset: Some(offset),
+                reason,
+            } => write!(f, "Invalid puzzle at offset {}: {}", offset, reason),
+            PuzzleError::Invalid { reason, .. } => write!(f, "Invalid puzzle: {}", reason),
+            PuzzleError::Conflict {
+                cell,
+                digit,
+                peer,
+                offset,
+            } => write!(
+                f,
+                "digit {} at {}{} conflicts with {}",
+                digit + 1,
+                cell.name(),
+                format_offset(*offset),
+                peer.name()
+            ),
+            PuzzleError::NoCandidates {
+                cell,
+                digit,
+                peer,
+                offset,
+            } => write!(
+                f,
+                "digit {} at {}{} leaves no possible digit for {}",
+                digit + 1,
+                cell.name(),
+                format_offset(*offset),
+                peer.name()
+            ),
+            PuzzleError::Unsolvable => write!(f, "Sudoku cannot be solved"),
+            PuzzleError::Ambiguous => write!(f, "Sudoku has multiple possible solutions"),
+            PuzzleError::Redundant => write!(f, "Sudoku has redundant givens"),
+            PuzzleError::Mistakes => write!(f, "The progress has mistakes"),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct NonUniqueError {}
-
-impl Error for NonUniqueError {}
-
-impl fmt::Display for NonUniqueError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Sudoku has multiple possible solutions")?;
-        Ok(())
+fn format_offset(offset: Option<usize>) -> String {
+    match offset {
+        Some(offset) => format!(" (offset {})", offset),
+        None => String::new(),
     }
 }
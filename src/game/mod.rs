@@ -0,0 +1,165 @@
+use crate::solver::board::{write_grid, Board};
+use crate::solver::calc::Cell;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Print, Stylize};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use std::error::Error;
+use std::fmt::Write as FmtWrite;
+use std::io::{stdout, Write};
+
+const HELP: &str = "Arrows/hjkl: move  1-9: enter digit  0/Space/Del: clear  q/Esc: quit";
+
+pub struct Game {
+    givens: Vec<u8>,
+    entries: Vec<u8>,
+    solution: Vec<u8>,
+    cursor: Cell,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Playing,
+    Wrong,
+    Solved,
+}
+
+impl Game {
+    pub fn new(puzzle: &Board, solution: &Board) -> Self {
+        let givens = puzzle.to_nums();
+        Game {
+            entries: givens.clone(),
+            givens,
+            solution: solution.to_nums(),
+            cursor: Cell(0),
+        }
+    }
+
+    pub fn move_cursor(&mut self, dx: i8, dy: i8) {
+        let x = (self.cursor.col().0 as i8 + dx).rem_euclid(9);
+        let y = (self.cursor.row().0 as i8 + dy).rem_euclid(9);
+        self.cursor = Cell((y * 9 + x) as u8);
+    }
+
+    // Puts a digit (1-9) into the cell under the cursor, or clears it with 0.
+    // The givens of the puzzle can't be changed.
+    pub fn enter(&mut self, val: u8) {
+        let idx = self.cursor.0 as usize;
+        if self.givens[idx] == 0 {
+            self.entries[idx] = val;
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        if self.entries.contains(&0) {
+            Status::Playing
+        } else if self.entries == self.solution {
+            Status::Solved
+        } else {
+            Status::Wrong
+        }
+    }
+
+    fn render_grid(&self) -> Result<String, std::fmt::Error> {
+        let mut out = String::new();
+        write_grid(&mut out, |out, cell| {
+            let idx = cell.0 as usize;
+            let text = match self.entries[idx] {
+                0 => " ".to_string(),
+                x => x.to_string(),
+            };
+            let styled = if self.givens[idx] != 0 {
+                text.bold()
+            } else {
+                text.cyan()
+            };
+            if cell == self.cursor {
+                write!(out, "{}", styled.reverse())
+            } else {
+                write!(out, "{}", styled)
+            }
+        })?;
+        Ok(out)
+    }
+
+    fn render<W: Write>(&self, w: &mut W) -> Result<(), Box<dyn Error>> {
+        queue!(w, Clear(ClearType::All))?;
+        let grid = self.render_grid()?;
+        let mut y = 0;
+        for line in grid.lines() {
+            queue!(w, MoveTo(0, y), Print(line))?;
+            y += 1;
+        }
+        let status = match self.status() {
+            Status::Playing => "".to_string(),
+            Status::Wrong => "The grid is full, but some digits are wrong"
+                .red()
+                .to_string(),
+            Status::Solved => "Solved! Press any key to quit".green().to_string(),
+        };
+        queue!(
+            w,
+            MoveTo(0, y + 1),
+            Print(status),
+            MoveTo(0, y + 2),
+            Print(HELP)
+        )?;
+        w.flush()?;
+        Ok(())
+    }
+
+    // Handles a single key press, returns false once the game should end
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.status() == Status::Solved {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(0, 1),
+            KeyCode::Char(c @ '0'..='9') => self.enter(c as u8 - b'0'),
+            KeyCode::Char(' ') | KeyCode::Backspace | KeyCode::Delete => self.enter(0),
+            _ => {}
+        }
+        true
+    }
+
+    pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        let _terminal = TerminalGuard::new()?;
+        let mut out = stdout();
+        loop {
+            self.render(&mut out)?;
+            if let Event::Key(key) = read()? {
+                if key.kind == KeyEventKind::Press && !self.handle_key(key) {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Puts the terminal into full-screen raw mode, and restores it when dropped, even if the game
+// exits with an error
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> Result<Self, Box<dyn Error>> {
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
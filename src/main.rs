@@ -1,13 +1,26 @@
 use clap::App;
+use errors::PuzzleError;
 use std::error::Error;
+use std::process;
 
 mod codex;
 mod commands;
 mod errors;
+mod game;
 mod solver;
 mod tools;
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("Error: {}", error);
+        let code = error
+            .downcast_ref::<PuzzleError>()
+            .map_or(1, PuzzleError::exit_code);
+        process::exit(code);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
     let mut app = App::new("ku")
         .version("0.2")
         .author("edave64 <edave64@gmail.com>")
@@ -16,11 +29,35 @@ fn main() -> Result<(), Box<dyn Error>> {
     app = commands::solve::register_command(app);
     app = commands::encode::register_command(app);
     app = commands::decode::register_command(app);
+    app = commands::generate::register_command(app);
+    app = commands::play::register_command(app);
+    app = commands::rate::register_command(app);
+    app = commands::hint::register_command(app);
+    app = commands::count::register_command(app);
+    app = commands::bench::register_command(app);
+    app = commands::check::register_command(app);
+    app = commands::minimize::register_command(app);
+    app = commands::fix::register_command(app);
+    app = commands::canon::register_command(app);
+    app = commands::transform::register_command(app);
+    app = commands::same::register_command(app);
 
     let matches = app.get_matches();
 
     commands::solve::execute(&matches)?;
     commands::encode::execute(&matches)?;
     commands::decode::execute(&matches)?;
+    commands::generate::execute(&matches)?;
+    commands::play::execute(&matches)?;
+    commands::rate::execute(&matches)?;
+    commands::hint::execute(&matches)?;
+    commands::count::execute(&matches)?;
+    commands::bench::execute(&matches)?;
+    commands::check::execute(&matches)?;
+    commands::minimize::execute(&matches)?;
+    commands::fix::execute(&matches)?;
+    commands::canon::execute(&matches)?;
+    commands::transform::execute(&matches)?;
+    commands::same::execute(&matches)?;
     Ok(())
 }
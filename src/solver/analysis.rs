@@ -0,0 +1,229 @@
+use crate::solver::board::Board;
+use crate::solver::calc::Cell;
+use crate::solver::solve::Engine;
+use std::str::FromStr;
+
+// Questions about puzzles that take many solver runs to answer. They work on the plain digits of
+// a puzzle, as they keep adding and removing givens.
+
+// Whether the puzzle is free of contradictions and has exactly one solution
+pub fn is_unique(nums: &[u8]) -> bool {
+    match Board::from_puzzle(nums.to_vec()) {
+        Ok(board) => matches!(Engine::Bitboard.solve(board, true), Ok(Some(_))),
+        Err(_) => false,
+    }
+}
+
+// Whether the puzzle stays unique without the given in this cell
+fn is_redundant(nums: &mut [u8], cell: Cell) -> bool {
+    let i = cell.0 as usize;
+    let given = nums[i];
+    if given == 0 {
+        return false;
+    }
+    nums[i] = 0;
+    let unique = is_unique(nums);
+    nums[i] = given;
+    unique
+}
+
+// The givens that could each be removed on their own, without the puzzle becoming ambiguous.
+// Removing several of them at once might not work.
+pub fn redundant_givens(nums: &[u8]) -> Vec<Cell> {
+    let mut nums = nums.to_vec();
+    (0..81)
+        .map(Cell)
+        .filter(|&cell| is_redundant(&mut nums, cell))
+        .collect()
+}
+
+// Patterns of givens that are mirrored or rotated onto themselves
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Symmetry {
+    None,
+    // Rotating by 180 degrees
+    Rotational,
+    // Rotating by 90 degrees
+    Quarter,
+    // Mirroring at the horizontal and vertical center lines
+    Horizontal,
+    Vertical,
+    // Mirroring at the main and the anti diagonal
+    Diagonal,
+    AntiDiagonal,
+}
+
+impl Symmetry {
+    pub const NAMES: [&'static str; 7] = [
+        "none",
+        "rotational",
+        "quarter",
+        "horizontal",
+        "vertical",
+        "diagonal",
+        "antidiagonal",
+    ];
+
+    // The cells this cell is mapped to, including itself
+    pub fn orbit(self, cell: Cell) -> Vec<Cell> {
+        let (row, col) = (cell.row().0, cell.col().0);
+        let at = |row: u8, col: u8| Cell(row * 9 + col);
+        let mut ret = match self {
+            Symmetry::None => vec![cell],
+            Symmetry::Rotational => vec![cell, at(8 - row, 8 - col)],
+            Symmetry::Quarter => vec![
+                cell,
+                at(col, 8 - row),
+                at(8 - row, 8 - col),
+                at(8 - col, row),
+            ],
+            Symmetry::Horizontal => vec![cell, at(8 - row, col)],
+            Symmetry::Vertical => vec![cell, at(row, 8 - col)],
+            Symmetry::Diagonal => vec![cell, at(col, row)],
+            Symmetry::AntiDiagonal => vec![cell, at(8 - col, 8 - row)],
+        };
+        ret.sort();
+        ret.dedup();
+        ret
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Symmetry::None),
+            "rotational" => Ok(Symmetry::Rotational),
+            "quarter" => Ok(Symmetry::Quarter),
+            "horizontal" => Ok(Symmetry::Horizontal),
+            "vertical" => Ok(Symmetry::Vertical),
+            "diagonal" => Ok(Symmetry::Diagonal),
+            "antidiagonal" => Ok(Symmetry::AntiDiagonal),
+            _ => Err(format!("Unknown symmetry '{}'", s)),
+        }
+    }
+}
+
+// Removes givens, one orbit of the symmetry at a time, as long as the puzzle stays unique. The
+// result has no orbit left that could be removed. With Symmetry::None, that makes it minimal.
+pub fn minimize(nums: &[u8], symmetry: Symmetry) -> Vec<u8> {
+    let mut nums = nums.to_vec();
+    for cell in (0..81).map(Cell) {
+        if nums[cell.0 as usize] == 0 {
+            continue;
+        }
+        let orbit = symmetry.orbit(cell);
+        let mut removed = nums.clone();
+        for other in &orbit {
+            removed[other.0 as usize] = 0;
+        }
+        if is_unique(&removed) {
+            nums = removed;
+        }
+    }
+    nums
+}
+
+// The empty cells that have a different digit in some other solution of the puzzle
+pub fn differing_cells(nums: &[u8], solution: &[u8]) -> Vec<Cell> {
+    let board = match Board::from_puzzle(nums.to_vec()) {
+        Ok(board) => board,
+        Err(_) => return vec![],
+    };
+    (0..81)
+        .map(Cell)
+        .filter(|&cell| {
+            let i = cell.0 as usize;
+            let mut other = board.clone();
+            nums[i] == 0
+                && other.eliminate(cell, solution[i] - 1).is_ok()
+                && matches!(Engine::Bitboard.solve(other, false), Ok(Some(_)))
+        })
+        .collect()
+}
+
+// The fewest cells to fill in from the solution, so that it becomes the only one. Every other
+// solution differs from it in some cells, and one of those has to be filled in to rule it out.
+// So the cells are a smallest hitting set for those differences. As there can be very many other
+// solutions, they are only collected as they turn up: each time the hitting set of the known ones
+// leaves the puzzle ambiguous, the next solution that it still allows joins them. Gives up once
+// more than `limit` cells would be needed, as the search grows exponentially.
+pub fn disambiguate(nums: &[u8], solution: &[u8], limit: usize) -> Option<Vec<Cell>> {
+    let mut differences: Vec<u128> = vec![];
+    let mut size = 0;
+    loop {
+        // More differences never make the hitting set smaller
+        let clues = smallest_hitting_set(&differences, size, limit)?;
+        size = clues.len();
+        let mut fixed = nums.to_vec();
+        for cell in &clues {
+            fixed[cell.0 as usize] = solution[cell.0 as usize];
+        }
+        let other = Board::from_puzzle(fixed).ok().and_then(|board| {
+            Engine::Bitboard
+                .solutions(board)
+                .map(|other| other.to_nums())
+                .find(|other| other != solution)
+        });
+        match other {
+            None => return Some(clues),
+            Some(other) => differences.push(
+                (0..81)
+                    .filter(|&i| other[i] != solution[i])
+                    .fold(0, |mask, i| mask | 1 << i),
+            ),
+        }
+    }
+}
+
+// The fewest cells that include at least one cell of every set, by trying ever larger amounts
+fn smallest_hitting_set(sets: &[u128], min: usize, max: usize) -> Option<Vec<Cell>> {
+    let mut chosen = vec![];
+    (min..=max)
+        .find(|&size| hit_all(sets, 0, 0, size, &mut chosen))
+        .map(|_| chosen)
+}
+
+// Cells that were already tried on a higher level are banned, so that every combination of cells
+// is only tried once
+fn hit_all(sets: &[u128], hit: u128, banned: u128, size: usize, chosen: &mut Vec<Cell>) -> bool {
+    let missed: Vec<u128> = sets
+        .iter()
+        .filter(|&&set| set & hit == 0)
+        .map(|&set| set & !banned)
+        .collect();
+    if missed.is_empty() {
+        return true;
+    }
+    // Sets without shared cells each need a cell of their own
+    let mut covered = 0;
+    let mut needed = 0;
+    for &set in &missed {
+        if set & covered == 0 {
+            covered |= set;
+            needed += 1;
+        }
+    }
+    if needed > size {
+        return false;
+    }
+    // Branching on the smallest set keeps the search narrow
+    let mut remaining = missed
+        .iter()
+        .copied()
+        .min_by_key(|set| set.count_ones())
+        .unwrap_or(0);
+    let mut banned = banned;
+    while remaining != 0 {
+        let cell = remaining.trailing_zeros();
+        remaining &= remaining - 1;
+        chosen.push(Cell(cell as u8));
+        if hit_all(sets, hit | 1 << cell, banned, size - 1, chosen) {
+            return true;
+        }
+        chosen.pop();
+        banned |= 1 << cell;
+    }
+    false
+}
@@ -0,0 +1,200 @@
+use crate::solver::board::{Board, CellState};
+use crate::solver::precalc::{CELL_BLOCK, CELL_COL, CELL_ROW, HOUSE_MASKS, PEER_MASKS};
+
+const ALL_CELLS: u128 = (1 << 81) - 1;
+
+// A compact board for fast searching: a bit set over the cells for every digit, instead of a
+// set of digits for every cell
+#[derive(Clone, Copy)]
+struct State {
+    // The cells each digit can still go in. Placed digits are removed.
+    candidates: [u128; 9],
+    // The cells each digit has been placed in
+    placed: [u128; 9],
+    unsolved: u128,
+    // The digits placed in each house, rows first, then columns, then blocks
+    houses: [u16; 27],
+}
+
+impl State {
+    fn new(board: &Board) -> Option<Self> {
+        let mut state = State {
+            candidates: [ALL_CELLS; 9],
+            placed: [0; 9],
+            unsolved: ALL_CELLS,
+            houses: [0; 27],
+        };
+        for (i, cell) in board.state.iter().enumerate() {
+            match *cell {
+                CellState::Solved(digit) => {
+                    if state.candidates[digit as usize] & 1 << i == 0 {
+                        return None;
+                    }
+                    state.place(i, digit as usize);
+                }
+                CellState::Unsolved(possibilities) => {
+                    for digit in (0..9).filter(|digit| possibilities.mask & 1 << digit == 0) {
+                        state.candidates[digit] &= !(1 << i);
+                    }
+                }
+            }
+        }
+        Some(state)
+    }
+
+    fn place(&mut self, cell: usize, digit: usize) {
+        let bit = 1 << cell;
+        for candidates in self.candidates.iter_mut() {
+            *candidates &= !bit;
+        }
+        self.candidates[digit] &= !PEER_MASKS[cell];
+        self.placed[digit] |= bit;
+        self.unsolved &= !bit;
+        for &house in &[
+            CELL_ROW[cell] as usize,
+            9 + CELL_COL[cell] as usize,
+            18 + CELL_BLOCK[cell] as usize,
+        ] {
+            self.houses[house] |= 1 << digit;
+        }
+    }
+
+    // Unsolved cells with exactly one candidate, and unsolved cells without any
+    fn singles(&self) -> (u128, u128) {
+        let mut once = 0;
+        let mut twice = 0;
+        for &candidates in &self.candidates {
+            twice |= once & candidates;
+            once |= candidates;
+        }
+        (once & !twice & self.unsolved, !once & self.unsolved)
+    }
+
+    // Places naked singles, and hidden ones if asked to, until there are none left. Returns false
+    // if the board turns out to be contradictory.
+    fn propagate(&mut self, hidden: bool) -> bool {
+        loop {
+            let (naked, empty) = self.singles();
+            if empty != 0 {
+                return false;
+            }
+            let mut progress = naked != 0;
+            let mut remaining = naked;
+            while remaining != 0 {
+                let cell = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                match (0..9).find(|&digit| self.candidates[digit] & 1 << cell != 0) {
+                    Some(digit) => self.place(cell, digit),
+                    // An earlier single in the same batch took its last candidate
+                    None => return false,
+                }
+            }
+            if !hidden {
+                if progress {
+                    continue;
+                }
+                return true;
+            }
+            for (house, &cells) in HOUSE_MASKS.iter().enumerate() {
+                for digit in 0..9 {
+                    if self.houses[house] & 1 << digit != 0 {
+                        continue;
+                    }
+                    let places = self.candidates[digit] & cells;
+                    if places == 0 {
+                        return false;
+                    }
+                    if places & (places - 1) == 0 {
+                        self.place(places.trailing_zeros() as usize, digit);
+                        progress = true;
+                    }
+                }
+            }
+            if !progress {
+                return true;
+            }
+        }
+    }
+
+    // The unsolved cell with the fewest candidates, and its candidates as a mask
+    fn most_certain(&self) -> Option<(usize, u16)> {
+        let mut best: Option<(usize, u16)> = None;
+        let mut remaining = self.unsolved;
+        while remaining != 0 {
+            let cell = remaining.trailing_zeros() as usize;
+            remaining &= remaining - 1;
+            let mask = (0..9)
+                .filter(|&digit| self.candidates[digit] & 1 << cell != 0)
+                .fold(0u16, |mask, digit| mask | 1 << digit);
+            if best.is_none_or(|(_, best)| best.count_ones() > mask.count_ones()) {
+                best = Some((cell, mask));
+                if mask.count_ones() <= 2 {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    fn board(&self) -> Board {
+        let mut board = Board::new();
+        for (digit, &cells) in self.placed.iter().enumerate() {
+            let mut remaining = cells;
+            while remaining != 0 {
+                let cell = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                board.state[cell] = CellState::Solved(digit as u8);
+            }
+        }
+        board
+    }
+}
+
+// Lazily yields every solution of a board, propagating singles before every guess
+pub struct Bitboard {
+    // States that still have to be explored, the next one on top
+    stack: Vec<State>,
+}
+
+impl Bitboard {
+    pub fn new(board: &Board) -> Self {
+        Bitboard {
+            stack: State::new(board).into_iter().collect(),
+        }
+    }
+}
+
+impl Iterator for Bitboard {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        while let Some(mut state) = self.stack.pop() {
+            // The guesses are made on the same candidates as in the backtracker, so the solutions
+            // come in the same order. Filling in hidden singles as well would leave other cells
+            // with the fewest candidates. They are filled in on a copy though, which finds dead
+            // ends a lot sooner.
+            if !state.propagate(false) {
+                continue;
+            }
+            let mut filled = state;
+            if !filled.propagate(true) {
+                continue;
+            }
+            let (cell, mask) = match state.most_certain() {
+                None => return Some(state.board()),
+                Some(next) => next,
+            };
+            // Every solution from here on has the singles that were filled in, so there is only one
+            if filled.unsolved == 0 {
+                return Some(filled.board());
+            }
+            // Pushed in reverse, so the smallest value ends up on top
+            for digit in (0..9).rev().filter(|digit| mask & 1 << digit != 0) {
+                let mut guess = state;
+                guess.place(cell, digit);
+                self.stack.push(guess);
+            }
+        }
+        None
+    }
+}
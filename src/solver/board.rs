@@ -1,4 +1,4 @@
-use crate::errors::{ContradicoryAssignmentError, UnsolvableError};
+use crate::errors::{ContradicoryAssignmentError, PuzzleError};
 use crate::solver::board::CellState::{Solved, Unsolved};
 use crate::solver::calc::{number_to_mask, Cell};
 use std::fmt;
@@ -32,21 +32,47 @@ impl Board {
         }
     }
 
-    pub fn from_puzzle(fields: Vec<u8>) -> Result<Self, UnsolvableError> {
+    pub fn from_puzzle(fields: Vec<u8>) -> Result<Self, PuzzleError> {
         let mut board = Board::new();
 
         for (i, &given) in fields.iter().enumerate() {
             if given != 0 {
-                let mark_err = board.mark(Cell((i) as u8), given - 1).is_err();
-                if mark_err {
-                    return Err(UnsolvableError {});
-                }
+                let cell = Cell(i as u8);
+                board.check_given(cell, given - 1, i)?;
+                board
+                    .mark(cell, given - 1)
+                    .map_err(|_| PuzzleError::Unsolvable)?;
             }
         }
 
         Ok(board)
     }
 
+    // Finds out why a given couldn't be marked, before marking it
+    fn check_given(&self, cell: Cell, val: u8, offset: usize) -> Result<(), PuzzleError> {
+        let peers = cell.peers().iter().copied();
+        if let Some(peer) = peers.clone().find(|&peer| self[peer] == Solved(val)) {
+            return Err(PuzzleError::Conflict {
+                cell,
+                digit: val,
+                peer,
+                offset: Some(offset),
+            });
+        }
+        if let Some(peer) = peers
+            .clone()
+            .find(|&peer| self.candidates(peer) == number_to_mask(val))
+        {
+            return Err(PuzzleError::NoCandidates {
+                cell,
+                digit: val,
+                peer,
+                offset: Some(offset),
+            });
+        }
+        Ok(())
+    }
+
     pub fn to_1d_string(&self) -> String {
         let mut a = String::new();
 
@@ -104,6 +130,7 @@ impl Board {
         }
     }
 
+    #[allow(dead_code)]
     pub fn first_unsolved(&self) -> Option<Cell> {
         for i in 0..81 {
             if let Unsolved(_) = self.state[i] {
@@ -114,6 +141,43 @@ impl Board {
         None
     }
 
+    pub fn is_solved(&self) -> bool {
+        self.state.iter().all(|state| matches!(state, Solved(_)))
+    }
+
+    // The remaining possible values of a cell, as a mask. Solved cells have none.
+    pub fn candidates(&self, cell: Cell) -> u16 {
+        match self[cell] {
+            Solved(_) => 0,
+            Unsolved(possibilities) => possibilities.mask,
+        }
+    }
+
+    // Removes a single possible value from a cell
+    pub fn eliminate(&mut self, cell: Cell, val: u8) -> Result<(), ContradicoryAssignmentError> {
+        match self.state[cell.0 as usize] {
+            Solved(solved) if solved == val => Err(ContradicoryAssignmentError {
+                target: cell,
+                attempted_val: val,
+                solved_val: Some(solved),
+            }),
+            Solved(_) => Ok(()),
+            Unsolved(mut possibilities) => {
+                possibilities.mask &= !number_to_mask(val);
+                possibilities.count = possibilities.mask.count_ones() as u8;
+                if possibilities.count == 0 {
+                    return Err(ContradicoryAssignmentError {
+                        target: cell,
+                        attempted_val: val,
+                        solved_val: None,
+                    });
+                }
+                self.state[cell.0 as usize] = Unsolved(possibilities);
+                Ok(())
+            }
+        }
+    }
+
     pub fn mark(&mut self, cell: Cell, val: u8) -> Result<u8, ContradicoryAssignmentError> {
         assert!(val <= 8);
         let current_val = self.state[cell.0 as usize];
@@ -132,10 +196,7 @@ impl Board {
                 if possibilies.mask & value_mask > 0 {
                     let mask_away = value_mask ^ 0xFFFF;
                     self.state[cell.0 as usize] = Solved(val);
-                    let a = self.mark_of_cells(mask_away, cell.row().into_iter());
-                    let b = self.mark_of_cells(mask_away, cell.col().into_iter());
-                    let c = self.mark_of_cells(mask_away, cell.block().into_iter());
-                    if a.is_err() || b.is_err() || c.is_err() {
+                    if self.mark_of_cells(mask_away, cell.peers()).is_err() {
                         return Err(ContradicoryAssignmentError {
                             target: cell,
                             attempted_val: val,
@@ -154,12 +215,8 @@ impl Board {
         Ok(val)
     }
 
-    pub fn mark_of_cells<T: Iterator<Item = Cell>>(
-        &mut self,
-        mask_off: u16,
-        iter: T,
-    ) -> Result<(), ()> {
-        for same_row_cell in iter {
+    pub fn mark_of_cells(&mut self, mask_off: u16, cells: &[Cell]) -> Result<(), ()> {
+        for &same_row_cell in cells {
             if let Unsolved(mut row_possibilities) = self.state[same_row_cell.0 as usize] {
                 row_possibilities.mask &= mask_off;
                 row_possibilities.count = row_possibilities.mask.count_ones() as u8;
@@ -181,26 +238,35 @@ impl Index<Cell> for Board {
     }
 }
 
+// Writes a grid in the layout used to display boards, leaving it to the caller to write the
+// contents of each cell
+pub fn write_grid<W: Write, F: FnMut(&mut W, Cell) -> fmt::Result>(
+    w: &mut W,
+    mut write_cell: F,
+) -> fmt::Result {
+    for y in 0..9u8 {
+        if y == 3 || y == 6 {
+            writeln!(w, "---+---+---")?;
+        }
+        for x in 0..9u8 {
+            if x == 3 || x == 6 {
+                write!(w, "|")?;
+            }
+            write_cell(w, Cell(y * 9 + x))?;
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for y in 0..9u8 {
-            if y == 3 || y == 6 {
-                writeln!(f, "---+---+---")?;
-            }
-            for x in 0..9u8 {
-                if x == 3 || x == 6 {
-                    write!(f, "|")?;
-                }
-                let idx = y * 9 + x;
-                let val = self.state[idx as usize];
-                if let Solved(num) = val {
-                    write!(f, "{}", num + 1)?;
-                } else {
-                    write!(f, " ")?;
-                }
+        write_grid(f, |f, cell| {
+            if let Solved(num) = self[cell] {
+                write!(f, "{}", num + 1)
+            } else {
+                write!(f, " ")
             }
-            writeln!(f)?;
-        }
-        Ok(())
+        })
     }
 }
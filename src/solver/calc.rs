@@ -1,5 +1,7 @@
-extern crate alloc;
-use alloc::vec::IntoIter;
+use crate::solver::precalc::{
+    BLOCK_CELLS, CELL_BLOCK, CELL_COL, CELL_ROW, COL_CELLS, PEERS, ROW_CELLS,
+};
+use std::array::IntoIter;
 use std::fmt;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -11,88 +13,127 @@ pub struct Col(pub u8);
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct Block(pub u8);
 
-pub trait House: IntoIterator<Item = Cell, IntoIter = IntoIter<Cell>> {
-    fn start(self) -> Cell;
+// The nine cells of a row, column or block
+pub trait House: IntoIterator<Item = Cell, IntoIter = IntoIter<Cell, 9>> + Copy {
+    fn contains(self, cell: Cell) -> bool;
+}
+
+// Any one of the 27 houses, for code that has to treat rows, columns and blocks alike
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Region {
+    Block(Block),
+    Row(Row),
+    Col(Col),
 }
 
 impl Cell {
     pub fn row(self) -> Row {
-        Row(self.0 / 9)
+        Row(CELL_ROW[self.0 as usize])
     }
 
     pub fn col(self) -> Col {
-        Col(self.0 % 9)
+        Col(CELL_COL[self.0 as usize])
     }
 
     pub fn block(self) -> Block {
-        let Row(row) = self.row();
-        let Col(col) = self.col();
+        Block(CELL_BLOCK[self.0 as usize])
+    }
+
+    // The cells that share a house with this one
+    pub fn peers(self) -> &'static [Cell; 20] {
+        &PEERS[self.0 as usize]
+    }
+
+    // The position of the cell in the usual row/column notation, e.g. r3c4
+    pub fn name(self) -> String {
+        format!("r{}c{}", self.row().0 + 1, self.col().0 + 1)
+    }
 
-        Block(row / 3 * 3 + col / 3)
+    // Whether the other cell shares a house with this one
+    pub fn sees(self, other: Cell) -> bool {
+        self != other
+            && (self.row() == other.row()
+                || self.col() == other.col()
+                || self.block() == other.block())
     }
 }
 
 impl IntoIterator for Row {
     type Item = Cell;
-    type IntoIter = IntoIter<Cell>;
+    type IntoIter = IntoIter<Cell, 9>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let start = self.start().0;
-        (start..(start + 9))
-            .into_iter()
-            .map(Cell)
-            .collect::<Vec<Cell>>()
-            .into_iter()
+        IntoIterator::into_iter(ROW_CELLS[self.0 as usize])
     }
 }
 
 impl House for Row {
-    fn start(self) -> Cell {
-        Cell(self.0 * 9)
+    fn contains(self, cell: Cell) -> bool {
+        cell.row() == self
     }
 }
 
 impl IntoIterator for Col {
     type Item = Cell;
-    type IntoIter = IntoIter<Cell>;
+    type IntoIter = IntoIter<Cell, 9>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let start = self.start().0;
-        (0..9)
-            .into_iter()
-            .map(|x| Cell(start + x * 9))
-            .collect::<Vec<Cell>>()
-            .into_iter()
+        IntoIterator::into_iter(COL_CELLS[self.0 as usize])
     }
 }
 
 impl House for Col {
-    fn start(self) -> Cell {
-        Cell(self.0)
+    fn contains(self, cell: Cell) -> bool {
+        cell.col() == self
     }
 }
 
 impl IntoIterator for Block {
     type Item = Cell;
-    type IntoIter = IntoIter<Cell>;
+    type IntoIter = IntoIter<Cell, 9>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let start = self.start().0;
-        (0..9)
-            .into_iter()
-            .map(|x| {
-                let block_row = x / 3;
-                let block_col = x % 3;
-                Cell(start + block_col + block_row * 9)
-            })
-            .collect::<Vec<Cell>>()
-            .into_iter()
+        IntoIterator::into_iter(BLOCK_CELLS[self.0 as usize])
     }
 }
 
 impl House for Block {
-    fn start(self) -> Cell {
-        Cell(self.0 / 3 * 27 + self.0 % 3 * 3)
+    fn contains(self, cell: Cell) -> bool {
+        cell.block() == self
+    }
+}
+
+impl Region {
+    // All houses, blocks first, then rows, then columns
+    pub fn all() -> Vec<Region> {
+        (0..9)
+            .map(|i| Region::Block(Block(i)))
+            .chain((0..9).map(|i| Region::Row(Row(i))))
+            .chain((0..9).map(|i| Region::Col(Col(i))))
+            .collect()
+    }
+}
+
+impl IntoIterator for Region {
+    type Item = Cell;
+    type IntoIter = IntoIter<Cell, 9>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Region::Block(block) => block.into_iter(),
+            Region::Row(row) => row.into_iter(),
+            Region::Col(col) => col.into_iter(),
+        }
+    }
+}
+
+impl House for Region {
+    fn contains(self, cell: Cell) -> bool {
+        match self {
+            Region::Block(block) => block.contains(cell),
+            Region::Row(row) => row.contains(cell),
+            Region::Col(col) => col.contains(cell),
+        }
     }
 }
 
@@ -100,6 +141,11 @@ pub fn number_to_mask(num: u8) -> u16 {
     1 << num
 }
 
+// The numbers contained in a mask, in ascending order
+pub fn mask_to_numbers(mask: u16) -> impl Iterator<Item = u8> {
+    (0..9).filter(move |&num| mask & number_to_mask(num) > 0)
+}
+
 impl fmt::Display for Cell {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.0 > 80 {
@@ -109,3 +155,13 @@ impl fmt::Display for Cell {
         }
     }
 }
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Region::Block(Block(i)) => write!(f, "b{}", i + 1),
+            Region::Row(Row(i)) => write!(f, "r{}", i + 1),
+            Region::Col(Col(i)) => write!(f, "c{}", i + 1),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::solver::board::{Board, CellState};
+use crate::solver::calc::Cell;
+
+// Sudoku as an exact cover problem: every cell has to hold exactly one digit, and every row,
+// column and block has to hold every digit exactly once. Each candidate (a digit in a cell) is a
+// row of the matrix, covering one of each of these constraints.
+const CONSTRAINTS: usize = 4 * 81;
+const ROOT: usize = 0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Fresh,
+    Found,
+    Done,
+}
+
+// Knuth's Algorithm X on a matrix of dancing links. Yields the solutions of a board one by one,
+// keeping the search state in between instead of recursing.
+pub struct Dlx {
+    // Node 0 is the root, 1 to 324 are the column headers, the rest are the candidates
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    // The candidate of each node, as cell * 9 + digit
+    candidate: Vec<usize>,
+    // The number of nodes left in each column
+    size: Vec<usize>,
+    // Candidates that are solved on the board we started from
+    givens: Vec<usize>,
+    // The node chosen on each level of the search
+    chosen: Vec<usize>,
+    state: State,
+}
+
+impl Dlx {
+    pub fn new(board: &Board) -> Self {
+        let headers = CONSTRAINTS + 1;
+        let mut dlx = Dlx {
+            left: (0..headers).map(|i| (i + headers - 1) % headers).collect(),
+            right: (0..headers).map(|i| (i + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            candidate: vec![0; headers],
+            size: vec![0; headers],
+            givens: vec![],
+            chosen: vec![],
+            state: State::Fresh,
+        };
+        let mut given_nodes = vec![];
+        for i in 0..81u8 {
+            let cell = Cell(i);
+            let digits = match board[cell] {
+                CellState::Solved(digit) => 1 << digit,
+                CellState::Unsolved(possibilities) => possibilities.mask,
+            };
+            for digit in (0..9).filter(|digit| digits & 1 << digit > 0) {
+                let node = dlx.add_row(cell, digit);
+                if let CellState::Solved(_) = board[cell] {
+                    given_nodes.push(node);
+                }
+            }
+        }
+        // The givens are already decided, so they are taken out before the search starts
+        for node in given_nodes {
+            dlx.cover(dlx.column[node]);
+            dlx.select(node);
+            dlx.givens.push(dlx.candidate[node]);
+        }
+        dlx
+    }
+
+    // Adds the row for a candidate, returning its first node
+    fn add_row(&mut self, cell: Cell, digit: u8) -> usize {
+        let digit = digit as usize;
+        let columns = [
+            1 + cell.0 as usize,
+            1 + 81 + cell.row().0 as usize * 9 + digit,
+            1 + 162 + cell.col().0 as usize * 9 + digit,
+            1 + 243 + cell.block().0 as usize * 9 + digit,
+        ];
+        let first = self.column.len();
+        for (i, &col) in columns.iter().enumerate() {
+            let node = first + i;
+            self.left.push(first + (i + 3) % 4);
+            self.right.push(first + (i + 1) % 4);
+            self.up.push(self.up[col]);
+            self.down.push(col);
+            self.column.push(col);
+            self.candidate.push(cell.0 as usize * 9 + digit);
+            let last = self.up[col];
+            self.down[last] = node;
+            self.up[col] = node;
+            self.size[col] += 1;
+        }
+        first
+    }
+
+    fn cover(&mut self, col: usize) {
+        self.right[self.left[col]] = self.right[col];
+        self.left[self.right[col]] = self.left[col];
+        let mut i = self.down[col];
+        while i != col {
+            let mut j = self.right[i];
+            while j != i {
+                self.down[self.up[j]] = self.down[j];
+                self.up[self.down[j]] = self.up[j];
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, col: usize) {
+        let mut i = self.up[col];
+        while i != col {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                self.down[self.up[j]] = j;
+                self.up[self.down[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        self.right[self.left[col]] = col;
+        self.left[self.right[col]] = col;
+    }
+
+    // Covers the other columns of a row, once its own column is covered
+    fn select(&mut self, node: usize) {
+        let mut j = self.right[node];
+        while j != node {
+            self.cover(self.column[j]);
+            j = self.right[j];
+        }
+    }
+
+    fn deselect(&mut self, node: usize) {
+        let mut j = self.left[node];
+        while j != node {
+            self.uncover(self.column[j]);
+            j = self.left[j];
+        }
+    }
+
+    // The column to guess in next. A constraint that no candidate can meet anymore ends the search
+    // right away, otherwise it is the cell with the fewest candidates, the first one on ties. This
+    // makes the guesses, and so the order of the solutions, the same as in the backtracker.
+    fn next_column(&self) -> usize {
+        let mut best = ROOT;
+        let mut col = self.right[ROOT];
+        while col != ROOT {
+            if self.size[col] == 0 {
+                return col;
+            }
+            // The cell constraints come first, in the order of the cells
+            if col <= 81 && (best == ROOT || self.size[col] < self.size[best]) {
+                best = col;
+                // Can't be beaten. A dead end elsewhere is still found further down.
+                if self.size[col] == 1 {
+                    break;
+                }
+            }
+            col = self.right[col];
+        }
+        best
+    }
+
+    fn board(&self) -> Board {
+        let mut board = Board::new();
+        let chosen = self.chosen.iter().map(|&node| self.candidate[node]);
+        for candidate in self.givens.iter().copied().chain(chosen) {
+            board.state[candidate / 9] = CellState::Solved((candidate % 9) as u8);
+        }
+        board
+    }
+}
+
+impl Iterator for Dlx {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        // After a solution, the search continues with the next option of the last choice
+        let mut backtracking = match self.state {
+            State::Fresh => false,
+            State::Found => true,
+            State::Done => return None,
+        };
+        loop {
+            if backtracking {
+                let node = match self.chosen.pop() {
+                    Some(node) => node,
+                    None => {
+                        self.state = State::Done;
+                        return None;
+                    }
+                };
+                self.deselect(node);
+                let col = self.column[node];
+                let next = self.down[node];
+                if next == col {
+                    // Every option for this column failed, so the choice before was wrong
+                    self.uncover(col);
+                    continue;
+                }
+                self.select(next);
+                self.chosen.push(next);
+                backtracking = false;
+                continue;
+            }
+            if self.right[ROOT] == ROOT {
+                self.state = State::Found;
+                return Some(self.board());
+            }
+            let col = self.next_column();
+            if self.size[col] == 0 {
+                backtracking = true;
+                continue;
+            }
+            self.cover(col);
+            // The rows of a cell were added in the order of their digits, so the smallest is
+            // tried first
+            let node = self.down[col];
+            self.select(node);
+            self.chosen.push(node);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::solver::analysis::is_unique;
+use crate::solver::board::Board;
+use crate::solver::calc::{Block, Cell};
+use crate::solver::logic::LogicOptions;
+use crate::solver::rating::rate;
+use crate::solver::solve::solve;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::str::FromStr;
+
+// How often we start over from a fresh grid when the requested band couldn't be reached
+const MAX_ATTEMPTS: u32 = 20;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    // The range the rating of a puzzle of this difficulty has to be in. Easy puzzles only need
+    // singles, medium ones intersections, subsets or basic fish, hard ones wings, coloring,
+    // uniqueness or finned fish, and expert ones chains, almost locked sets or guessing.
+    pub fn ratings(self) -> (f32, f32) {
+        match self {
+            Difficulty::Easy => (1.0, 2.3),
+            Difficulty::Medium => (2.6, 4.0),
+            Difficulty::Hard => (4.2, 6.2),
+            Difficulty::Expert => (6.5, f32::INFINITY),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            "expert" => Ok(Difficulty::Expert),
+            _ => Err(format!("Unknown difficulty '{}'", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Target {
+    Difficulty(Difficulty),
+    Clues(u8),
+}
+
+impl Target {
+    // Whether a clue may still be removed from the puzzle. Removing clues mostly makes a puzzle
+    // harder, so a puzzle that got too hard isn't dug any further.
+    fn allows(self, nums: &[u8]) -> bool {
+        match self {
+            Target::Difficulty(difficulty) => rating(nums) <= difficulty.ratings().1,
+            Target::Clues(clues) => clue_count(nums) >= clues,
+        }
+    }
+
+    // How far the puzzle is from the target, zero or less once it is reached
+    fn shortfall(self, nums: &[u8]) -> f32 {
+        match self {
+            Target::Difficulty(difficulty) => difficulty.ratings().0 - rating(nums),
+            Target::Clues(clues) => clue_count(nums) as f32 - clues as f32,
+        }
+    }
+}
+
+// Creates a random, completely filled grid.
+// The three blocks on the diagonal don't share any houses, so they can be filled with random
+// permutations without ever producing a contradiction. The solver then fills in the rest.
+pub fn random_grid<R: Rng>(rng: &mut R) -> Board {
+    let mut board = Board::new();
+    for &block in &[Block(0), Block(4), Block(8)] {
+        let mut digits: Vec<u8> = (0..9).collect();
+        digits.shuffle(rng);
+        for (cell, digit) in block.into_iter().zip(digits) {
+            board
+                .mark(cell, digit)
+                .expect("Diagonal blocks can't conflict");
+        }
+    }
+    solve(board, false)
+        .expect("Solving without proving uniqueness can't fail")
+        .expect("Every filling of the diagonal blocks is solvable")
+}
+
+// Removes clues from a random grid in a random order, as long as the puzzle stays uniquely
+// solvable and the target allows it. Stops once no more clues can be removed.
+fn dig<R: Rng>(rng: &mut R, target: Target) -> Vec<u8> {
+    let mut nums = random_grid(rng).to_nums();
+    let mut order: Vec<Cell> = (0..81).map(Cell).collect();
+    order.shuffle(rng);
+
+    for cell in order {
+        let given = nums[cell.0 as usize];
+        nums[cell.0 as usize] = 0;
+        if !is_unique(&nums) || !target.allows(&nums) {
+            nums[cell.0 as usize] = given;
+        }
+    }
+
+    nums
+}
+
+// Generates a new, uniquely solvable puzzle. If the target can't be reached within a few attempts,
+// the puzzle that came closest is returned.
+pub fn generate<R: Rng>(rng: &mut R, target: Target) -> Board {
+    let mut best: Option<(f32, Vec<u8>)> = None;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let nums = dig(rng, target);
+        let shortfall = target.shortfall(&nums);
+        if best.as_ref().is_none_or(|(best, _)| *best > shortfall) {
+            best = Some((shortfall, nums));
+        }
+        if shortfall <= 0.0 {
+            break;
+        }
+    }
+    let (_, best) = best.expect("At least one attempt is made");
+
+    Board::from_puzzle(best).expect("Generated puzzles are always valid")
+}
+
+// The rating of a puzzle, or infinity if it can't be solved by logic alone
+fn rating(nums: &[u8]) -> f32 {
+    let board = Board::from_puzzle(nums.to_vec()).expect("Dug puzzles are always valid");
+    // Digging only keeps puzzles with a single solution
+    let options = LogicOptions {
+        assume_unique: true,
+    };
+    match rate(board, &options) {
+        Ok(rating) if !rating.needs_guessing => rating.rating,
+        _ => f32::INFINITY,
+    }
+}
+
+fn clue_count(nums: &[u8]) -> u8 {
+    nums.iter().filter(|&&x| x != 0).count() as u8
+}
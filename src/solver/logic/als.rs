@@ -0,0 +1,391 @@
+use crate::solver::board::Board;
+use crate::solver::calc::{mask_to_numbers, number_to_mask, Cell, Region};
+use crate::solver::logic::{format_cells, format_digits, Pattern, Step, Technique};
+use std::fmt;
+
+// Larger sets are plentiful, slow to combine and next to impossible to spot by hand
+const MAX_ALS_SIZE: usize = 6;
+const MAX_STEM_CANDIDATES: u32 = 3;
+
+// An Almost Locked Set: N cells in a house that have N + 1 candidates between them. If any of the
+// candidates is removed, the remaining ones are locked into those cells.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Als {
+    pub house: Region,
+    pub cells: Vec<Cell>,
+    pub digits: u16,
+    // The cells as a bit set, for quick overlap checks
+    set: u128,
+}
+
+impl Als {
+    pub fn cells_with(&self, board: &Board, digit: u8) -> Vec<Cell> {
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&cell| board.candidates(cell) & number_to_mask(digit) > 0)
+            .collect()
+    }
+
+    pub fn contains(&self, cell: Cell) -> bool {
+        self.set & (1 << cell.0) > 0
+    }
+
+    pub fn overlaps(&self, other: &Als) -> bool {
+        self.set & other.set > 0
+    }
+}
+
+impl fmt::Display for Als {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            format_digits(self.digits),
+            format_cells(&self.cells)
+        )
+    }
+}
+
+// Every almost locked set in every row, column and block. Sets that lie in more than one house
+// are only listed once.
+pub fn almost_locked_sets(board: &Board) -> Vec<Als> {
+    let mut ret: Vec<Als> = vec![];
+    for house in Region::all() {
+        let unsolved: Vec<Cell> = house
+            .into_iter()
+            .filter(|&cell| board.candidates(cell) != 0)
+            .collect();
+        // Every subset of the unsolved cells, as a bit mask over their positions
+        for subset in 1u32..(1 << unsolved.len()) {
+            let size = subset.count_ones() as usize;
+            if size > MAX_ALS_SIZE || size >= unsolved.len() {
+                continue;
+            }
+            let cells: Vec<Cell> = unsolved
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| subset & (1 << i) > 0)
+                .map(|(_, &cell)| cell)
+                .collect();
+            let digits = cells
+                .iter()
+                .fold(0u16, |mask, &cell| mask | board.candidates(cell));
+            if digits.count_ones() as usize != size + 1 {
+                continue;
+            }
+            let set = cells.iter().fold(0u128, |set, cell| set | 1 << cell.0);
+            if ret.iter().all(|als| als.set != set) {
+                ret.push(Als {
+                    house,
+                    cells,
+                    digits,
+                    set,
+                });
+            }
+        }
+    }
+    ret
+}
+
+// Restricted common candidates: digits in both sets, where every place for the digit in one set
+// sees every place in the other. At most one of the sets can hold such a digit.
+fn restricted_commons(board: &Board, a: &Als, b: &Als) -> u16 {
+    mask_to_numbers(a.digits & b.digits)
+        .filter(|&digit| {
+            let b_cells = b.cells_with(board, digit);
+            a.cells_with(board, digit)
+                .iter()
+                .all(|&x| b_cells.iter().all(|&y| x.sees(y)))
+        })
+        .fold(0, |mask, digit| mask | number_to_mask(digit))
+}
+
+// Removes the digit from every cell outside the sets that sees all of its places in the sets
+fn eliminate_seeing_all(board: &Board, digit: u8, sets: &[&Als]) -> Vec<(Cell, u8)> {
+    let places: Vec<Cell> = sets
+        .iter()
+        .flat_map(|als| als.cells_with(board, digit))
+        .collect();
+    if places.is_empty() {
+        return vec![];
+    }
+    (0..81)
+        .map(Cell)
+        .filter(|&cell| board.candidates(cell) & number_to_mask(digit) > 0)
+        .filter(|&cell| sets.iter().all(|als| !als.contains(cell)))
+        .filter(|&cell| places.iter().all(|&place| place.sees(cell)))
+        .map(|cell| (cell, digit))
+        .collect()
+}
+
+fn merge(eliminations: &mut Vec<(Cell, u8)>, more: Vec<(Cell, u8)>) {
+    for elimination in more {
+        if !eliminations.contains(&elimination) {
+            eliminations.push(elimination);
+        }
+    }
+}
+
+fn als_step(
+    technique: Technique,
+    stem: Option<Cell>,
+    sets: Vec<Als>,
+    restricted: u16,
+    eliminations: Vec<(Cell, u8)>,
+) -> Option<Step> {
+    if eliminations.is_empty() {
+        return None;
+    }
+    Some(Step {
+        technique,
+        pattern: Pattern::Als {
+            stem,
+            sets,
+            restricted,
+        },
+        placement: None,
+        eliminations,
+    })
+}
+
+// Two sets A and B, connected by a restricted common candidate x. One of them has to lose x and
+// become locked, so any other common digit z has to be in one of them. With two restricted
+// commons, both sets are locked.
+pub fn als_xz(board: &Board) -> Option<Step> {
+    let sets = almost_locked_sets(board);
+    for (i, a) in sets.iter().enumerate() {
+        for b in &sets[i + 1..] {
+            if a.overlaps(b) {
+                continue;
+            }
+            let restricted = restricted_commons(board, a, b);
+            if restricted == 0 {
+                continue;
+            }
+            let mut eliminations = vec![];
+            if restricted.count_ones() == 1 {
+                for z in mask_to_numbers(a.digits & b.digits & !restricted) {
+                    merge(&mut eliminations, eliminate_seeing_all(board, z, &[a, b]));
+                }
+            } else {
+                for x in mask_to_numbers(restricted) {
+                    merge(&mut eliminations, eliminate_seeing_all(board, x, &[a, b]));
+                }
+                for als in &[a, b] {
+                    for z in mask_to_numbers(als.digits & !restricted) {
+                        merge(&mut eliminations, eliminate_seeing_all(board, z, &[als]));
+                    }
+                }
+            }
+            let step = als_step(
+                Technique::AlsXz,
+                None,
+                vec![a.clone(), b.clone()],
+                restricted,
+                eliminations,
+            );
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+    None
+}
+
+// Sets A and B are both connected to a pivot set C, by different restricted common candidates.
+// Either A or B has to become locked, so a digit z common to both has to be in one of them.
+pub fn als_xy_wing(board: &Board) -> Option<Step> {
+    let sets = almost_locked_sets(board);
+    for c in &sets {
+        let linked: Vec<(&Als, u16)> = sets
+            .iter()
+            .filter(|other| !other.overlaps(c))
+            .map(|other| (other, restricted_commons(board, c, other)))
+            .filter(|&(_, restricted)| restricted != 0)
+            .collect();
+        for (i, &(a, a_links)) in linked.iter().enumerate() {
+            for &(b, b_links) in &linked[i + 1..] {
+                if a.overlaps(b) {
+                    continue;
+                }
+                for x in mask_to_numbers(a_links) {
+                    for y in mask_to_numbers(b_links).filter(|&y| y != x) {
+                        let links = number_to_mask(x) | number_to_mask(y);
+                        let mut eliminations = vec![];
+                        for z in mask_to_numbers(a.digits & b.digits & !links) {
+                            merge(&mut eliminations, eliminate_seeing_all(board, z, &[a, b]));
+                        }
+                        let step = als_step(
+                            Technique::AlsXyWing,
+                            None,
+                            vec![a.clone(), b.clone(), c.clone()],
+                            links,
+                            eliminations,
+                        );
+                        if step.is_some() {
+                            return step;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+// A stem cell, and a petal set for each of its candidates, in which every place for that
+// candidate sees the stem. Whatever the stem holds, the matching petal is locked, so a digit common
+// to all petals has to be in one of them.
+pub fn death_blossom(board: &Board) -> Option<Step> {
+    let sets = almost_locked_sets(board);
+    for stem in (0..81).map(Cell) {
+        let candidates = board.candidates(stem);
+        if candidates.count_ones() < 2 || candidates.count_ones() > MAX_STEM_CANDIDATES {
+            continue;
+        }
+        let petals: Vec<Vec<&Als>> = mask_to_numbers(candidates)
+            .map(|digit| {
+                sets.iter()
+                    .filter(|als| !als.contains(stem) && als.digits & !candidates != 0)
+                    .filter(|als| {
+                        let places = als.cells_with(board, digit);
+                        !places.is_empty() && places.iter().all(|&place| place.sees(stem))
+                    })
+                    .collect()
+            })
+            .collect();
+        if petals.iter().any(|options| options.is_empty()) {
+            continue;
+        }
+        let mut chosen: Vec<&Als> = vec![];
+        if let Some(step) = blossom(board, stem, candidates, &petals, &mut chosen) {
+            return Some(step);
+        }
+    }
+    None
+}
+
+// Tries every combination of disjoint petals, one for each stem candidate
+fn blossom<'a>(
+    board: &Board,
+    stem: Cell,
+    candidates: u16,
+    petals: &[Vec<&'a Als>],
+    chosen: &mut Vec<&'a Als>,
+) -> Option<Step> {
+    let common = chosen
+        .iter()
+        .fold(0x1FF & !candidates, |mask, als| mask & als.digits);
+    if common == 0 {
+        return None;
+    }
+    if chosen.len() == petals.len() {
+        let mut eliminations = vec![];
+        for z in mask_to_numbers(common) {
+            let more = eliminate_seeing_all(board, z, chosen)
+                .into_iter()
+                .filter(|&(cell, _)| cell != stem)
+                .collect();
+            merge(&mut eliminations, more);
+        }
+        return als_step(
+            Technique::DeathBlossom,
+            Some(stem),
+            chosen.iter().map(|&als| als.clone()).collect(),
+            candidates,
+            eliminations,
+        );
+    }
+    for &petal in &petals[chosen.len()] {
+        if chosen.iter().any(|other| other.overlaps(petal)) {
+            continue;
+        }
+        chosen.push(petal);
+        let step = blossom(board, stem, candidates, petals, chosen);
+        chosen.pop();
+        if step.is_some() {
+            return step;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::logic::tests::{assert_step, grid};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn als_xz() {
+        let board = grid(
+            "
+            5 2   3  | 147 17    6   | 179 49   8
+            4 8   67 | 9   12357 25  | 137 125  56
+            1 67  9  | 34  8     245 | 37  24   456
+            ---------+---------------+-------------
+            7 5   28 | 12  6     3   | 4   189  19
+            3 9   24 | 8   1257  245 | 6   157  57
+            6 1   48 | 47  57    9   | 2   3578 357
+            ---------+---------------+-------------
+            8 4   5  | 23  23    7   | 19  6    19
+            9 367 67 | 5   4     1   | 8   37   2
+            2 37  1  | 6   9     8   | 5   347  347
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::AlsXz,
+            "ALS-XZ: {147} r1c4,r1c5 / {347} r3c4,r3c7 linked by {4} => r1c7<>7",
+        );
+    }
+
+    #[test]
+    fn als_xy_wing() {
+        let board = grid(
+            "
+            69 1689 68  | 46  5  3   | 2   146  7
+            35 136  2   | 8   7  146 | 146 9    145
+            4  7    56  | 69  2  169 | 168 1568 3
+            ------------+------------+--------------
+            1  2    9   | 35  4  7   | 368 58   56
+            35 38   458 | 2   69 69  | 134 7    145
+            7  36   456 | 35  1  8   | 9   45   2
+            ------------+------------+--------------
+            2  69   3   | 7   8  5   | 146 146  1469
+            8  4    7   | 1   69 2   | 5   3    69
+            69 5    1   | 469 3  469 | 7   2    8
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::AlsXyWing,
+            "ALS-XY-Wing: {69} r9c1 / {469} r8c5,r9c4 / {469} r1c1,r1c4 linked by {49} => r9c6<>6",
+        );
+    }
+
+    #[test]
+    fn death_blossom() {
+        let board = grid(
+            "
+            2   4    1   | 58    3   57 | 789  6    89
+            5   9    6   | 18    17  4  | 2    378  38
+            3   8    7   | 2     6   9  | 1    4    5
+            -------------+--------------+----------------
+            4   5    38  | 7     9   36 | 68   1    2
+            6   37   2   | 345   8   1  | 579  579  49
+            179 17   89  | 456   45  2  | 3    578  468
+            -------------+--------------+----------------
+            8   12   359 | 14569 124 36 | 4569 359  7
+            179 6    4   | 359   157 8  | 59   2    13
+            179 1237 359 | 14569 245 57 | 4689 3589 13689
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::DeathBlossom,
+            "Death Blossom: stem r5c4 {345} with petals {368} r4c6,r4c7 / {489} r1c9,r5c9 / {58} \
+             r1c4 => r1c7<>8",
+        );
+    }
+}
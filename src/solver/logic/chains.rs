@@ -0,0 +1,362 @@
+use crate::solver::board::Board;
+use crate::solver::calc::{mask_to_numbers, number_to_mask, Cell, Region};
+use crate::solver::logic::{places_for, Pattern, Step, Technique};
+use std::collections::VecDeque;
+
+// A single candidate: a digit in a cell
+pub type Node = (Cell, u8);
+
+// A chain, and the candidates it eliminates
+type Found = (Vec<Node>, Vec<(Cell, u8)>);
+
+// Longer chains are hardly ever needed, and impossible to follow for a human anyway
+const MAX_LINKS: usize = 15;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ChainKind {
+    // Only a single digit, linked through houses
+    X(u8),
+    // Only bivalue cells, strongly linked inside the cells and weakly between them
+    XY,
+    // Everything goes
+    Aic,
+}
+
+fn node_index((cell, digit): Node) -> usize {
+    cell.0 as usize * 9 + digit as usize
+}
+
+fn has_candidate(board: &Board, (cell, digit): Node) -> bool {
+    board.candidates(cell) & number_to_mask(digit) > 0
+}
+
+fn houses(cell: Cell) -> [Region; 3] {
+    [
+        Region::Row(cell.row()),
+        Region::Col(cell.col()),
+        Region::Block(cell.block()),
+    ]
+}
+
+fn allowed(board: &Board, kind: ChainKind, (cell, digit): Node) -> bool {
+    match kind {
+        ChainKind::X(x) => digit == x,
+        ChainKind::XY => board.candidates(cell).count_ones() == 2,
+        ChainKind::Aic => true,
+    }
+}
+
+// Candidates that have to be true if the given one is false
+fn strong_links(board: &Board, kind: ChainKind, (cell, digit): Node) -> Vec<Node> {
+    let mut ret = vec![];
+    if kind != ChainKind::XY {
+        for &house in &houses(cell) {
+            if let [first, second] = places_for(board, house, digit)[..] {
+                let other = if first == cell { second } else { first };
+                if !ret.contains(&(other, digit)) {
+                    ret.push((other, digit));
+                }
+            }
+        }
+    }
+    let candidates = board.candidates(cell);
+    if !matches!(kind, ChainKind::X(_)) && candidates.count_ones() == 2 {
+        ret.extend(mask_to_numbers(candidates & !number_to_mask(digit)).map(|other| (cell, other)));
+    }
+    ret
+}
+
+// Candidates that have to be false if the given one is true
+fn weak_links(board: &Board, kind: ChainKind, (cell, digit): Node) -> Vec<Node> {
+    let mut ret: Vec<Node> = (0..81)
+        .map(Cell)
+        .filter(|&other| cell.sees(other))
+        .map(|other| (other, digit))
+        .filter(|&node| has_candidate(board, node) && allowed(board, kind, node))
+        .collect();
+    if kind == ChainKind::Aic {
+        ret.extend(
+            mask_to_numbers(board.candidates(cell) & !number_to_mask(digit))
+                .map(|other| (cell, other)),
+        );
+    }
+    ret
+}
+
+// A chain proves that at least one of its ends is true, so everything that can't be true
+// together with either of them goes
+fn chain_eliminations(board: &Board, start: Node, end: Node) -> Vec<(Cell, u8)> {
+    let ((start_cell, x), (end_cell, y)) = (start, end);
+    if x == y {
+        (0..81)
+            .map(Cell)
+            .filter(|&cell| cell.sees(start_cell) && cell.sees(end_cell))
+            .map(|cell| (cell, x))
+            .filter(|&node| has_candidate(board, node))
+            .collect()
+    } else if start_cell == end_cell {
+        mask_to_numbers(board.candidates(start_cell) & !number_to_mask(x) & !number_to_mask(y))
+            .map(|digit| (start_cell, digit))
+            .collect()
+    } else if start_cell.sees(end_cell) {
+        [(end_cell, x), (start_cell, y)]
+            .iter()
+            .copied()
+            .filter(|&node| has_candidate(board, node))
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+// Breadth first search for alternating chains, starting and ending with a strong link.
+// Returns the shortest chain with eliminations, for each starting candidate.
+fn shortest_chain(board: &Board, kind: ChainKind, start: Node) -> Option<Found> {
+    // Each candidate is visited at most twice: once reached by a strong link, once by a weak one
+    let mut parents: Vec<Option<(Node, bool)>> = vec![None; 729 * 2];
+    let state = |node: Node, strong: bool| node_index(node) * 2 + strong as usize;
+    let mut queue = VecDeque::new();
+    queue.push_back((start, false, 0));
+
+    while let Some((node, arrived_strong, links)) = queue.pop_front() {
+        if arrived_strong && links >= 3 {
+            let eliminations = if kind == ChainKind::XY && node.1 != start.1 {
+                vec![]
+            } else {
+                chain_eliminations(board, start, node)
+            };
+            if !eliminations.is_empty() {
+                let mut chain = vec![node];
+                let mut current = (node, arrived_strong);
+                while let Some(parent) = parents[state(current.0, current.1)] {
+                    chain.push(parent.0);
+                    current = parent;
+                }
+                chain.reverse();
+                let mut unique = chain.clone();
+                unique.sort();
+                unique.dedup();
+                if unique.len() == chain.len() {
+                    return Some((chain, eliminations));
+                }
+            }
+        }
+        if links >= MAX_LINKS {
+            continue;
+        }
+        let next = if arrived_strong {
+            weak_links(board, kind, node)
+        } else {
+            strong_links(board, kind, node)
+        };
+        for next_node in next {
+            let next_state = state(next_node, !arrived_strong);
+            if next_node == start || parents[next_state].is_some() {
+                continue;
+            }
+            parents[next_state] = Some((node, arrived_strong));
+            queue.push_back((next_node, !arrived_strong, links + 1));
+        }
+    }
+    None
+}
+
+fn chain(board: &Board, kind: ChainKind, technique: Technique) -> Option<Step> {
+    let mut best: Option<Found> = None;
+    for cell in (0..81).map(Cell) {
+        for digit in mask_to_numbers(board.candidates(cell)) {
+            let start = (cell, digit);
+            if !allowed(board, kind, start) {
+                continue;
+            }
+            if let Some(found) = shortest_chain(board, kind, start) {
+                if best
+                    .as_ref()
+                    .is_none_or(|best| best.0.len() > found.0.len())
+                {
+                    best = Some(found);
+                }
+            }
+        }
+    }
+    best.map(|(nodes, eliminations)| Step {
+        technique,
+        pattern: Pattern::Chain { nodes },
+        placement: None,
+        eliminations,
+    })
+}
+
+pub fn x_chain(board: &Board) -> Option<Step> {
+    (0..9).find_map(|digit| chain(board, ChainKind::X(digit), Technique::XChain))
+}
+
+pub fn xy_chain(board: &Board) -> Option<Step> {
+    chain(board, ChainKind::XY, Technique::XYChain)
+}
+
+pub fn aic(board: &Board) -> Option<Step> {
+    chain(board, ChainKind::Aic, Technique::Aic)
+}
+
+// Colors the candidates of a digit that are connected by strong links in two alternating
+// colors. One of the colors has to be true. If two candidates of the same color see each other,
+// that color is false. Candidates that see both colors are false as well.
+pub fn simple_coloring(board: &Board) -> Option<Step> {
+    for digit in 0..9 {
+        let kind = ChainKind::X(digit);
+        let mut colored = [false; 81];
+        for cell in (0..81).map(Cell) {
+            if colored[cell.0 as usize] || !has_candidate(board, (cell, digit)) {
+                continue;
+            }
+            let mut colors: [Vec<Cell>; 2] = [vec![cell], vec![]];
+            let mut queue = VecDeque::new();
+            colored[cell.0 as usize] = true;
+            queue.push_back((cell, 0));
+            while let Some((current, color)) = queue.pop_front() {
+                for (next, _) in strong_links(board, kind, (current, digit)) {
+                    if !colored[next.0 as usize] {
+                        colored[next.0 as usize] = true;
+                        colors[1 - color].push(next);
+                        queue.push_back((next, 1 - color));
+                    }
+                }
+            }
+            if colors[1].is_empty() {
+                continue;
+            }
+
+            let wrapped = (0..2).find(|&color| {
+                let cells = &colors[color];
+                cells.iter().any(|&a| cells.iter().any(|&b| a.sees(b)))
+            });
+            let eliminations: Vec<(Cell, u8)> = match wrapped {
+                Some(color) => colors[color].iter().map(|&cell| (cell, digit)).collect(),
+                None => (0..81)
+                    .map(Cell)
+                    .filter(|&other| has_candidate(board, (other, digit)))
+                    .filter(|other| !colors[0].contains(other) && !colors[1].contains(other))
+                    .filter(|&other| {
+                        colors[0].iter().any(|&cell| cell.sees(other))
+                            && colors[1].iter().any(|&cell| cell.sees(other))
+                    })
+                    .map(|other| (other, digit))
+                    .collect(),
+            };
+            if !eliminations.is_empty() {
+                let [on, off] = colors;
+                return Some(Step {
+                    technique: Technique::SimpleColoring,
+                    pattern: Pattern::Coloring { digit, on, off },
+                    placement: None,
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::logic::tests::{assert_step, grid};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn simple_coloring() {
+        let board = grid(
+            "
+            9  8   2457 | 3   6   245  | 247  1     27
+            6  457 2457 | 145 124 8    | 2479 279   3
+            1  3   24   | 9   7   24   | 5    6     8
+            ------------+--------------+----------------
+            25 479 479  | 8   49  1    | 3    25    6
+            25 149 6    | 7   3   49   | 1289 2589  1259
+            3  19  8    | 2   5   6    | 179  4     179
+            ------------+--------------+----------------
+            7  6   59   | 45  8   2459 | 129  3     1259
+            8  2   3    | 15  19  7    | 6    59    4
+            4  59  1    | 6   29  3    | 2789 25789 2579
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::SimpleColoring,
+            "Simple Coloring: 4 colored r2c5,r5c6 / r4c5,r5c2 => r2c2<>4",
+        );
+    }
+
+    #[test]
+    fn x_chain() {
+        let board = grid(
+            "
+            29   38  6    | 18    39 7  | 145 1245 124
+            15   4   38   | 12568 23 16 | 7   9    126
+            7    15  29   | 1256  69 4  | 3   8    126
+            --------------+-------------+-------------
+            246  67  5    | 26    1  9  | 48  3    78
+            12   36  137  | 4     26 8  | 9   127  5
+            8    19  1249 | 3     7  5  | 6   124  124
+            --------------+-------------+-------------
+            3    678 1489 | 169   5  16 | 2   14   78
+            16   2   178  | 167   4  3  | 58  57   9
+            1459 59  1479 | 179   8  2  | 14  6    3
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::XChain,
+            "X-Chain: (2)r3c3=(2)r6c3-(2)r4c1=(2)r4c4 => r3c4<>2",
+        );
+    }
+
+    #[test]
+    fn xy_chain() {
+        let board = grid(
+            "
+            1 6 3  | 7   459  2    | 45  8  59
+            4 2 59 | 189 5689 1568 | 3   69 7
+            8 7 59 | 49  3    56   | 456 1  2
+            -------+---------------+----------
+            3 4 8  | 6   25   7    | 1   29 59
+            6 5 1  | 34  24   9    | 7   23 8
+            2 9 7  | 138 58   158  | 56  36 4
+            -------+---------------+----------
+            9 1 6  | 2   7    4    | 8   5  3
+            5 3 4  | 89  689  68   | 2   7  1
+            7 8 2  | 5   1    3    | 9   4  6
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::XYChain,
+            "XY-Chain: (9)r3c3=(5)r3c3-(5)r3c6=(6)r3c6-(6)r8c6=(8)r8c6-(8)r8c4=(9)r8c4 => r3c4<>9",
+        );
+    }
+
+    #[test]
+    fn aic() {
+        let board = grid(
+            "
+            9     46   5    | 47   1   8   | 467  2    3
+            36    7    346  | 2    5   49  | 8    469  1
+            1     28   28   | 479  6   3   | 5    49   79
+            ----------------+--------------+----------------
+            356   3456 3469 | 1359 8   2   | 139  7    69
+            2     368  7    | 1349 39  149 | 1369 5    68
+            358   1    389  | 359  7   6   | 2    389  4
+            ----------------+--------------+----------------
+            4     2368 2368 | 1389 239 5   | 3679 138  26789
+            378   9    238  | 6    23  17  | 34   1348 5
+            35678 2358 1    | 389  4   79  | 369  3689 2689
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::Aic,
+            "Alternating Inference Chain: (8)r5c9=(8)r5c2-(8)r3c2=(2)r3c2-(2)r9c2=(2)r9c9 => \
+             r9c9<>8",
+        );
+    }
+}
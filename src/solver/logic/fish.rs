@@ -0,0 +1,386 @@
+use crate::solver::board::Board;
+use crate::solver::calc::{number_to_mask, Cell, Col, Region, Row};
+use crate::solver::logic::{combinations, places_for, Pattern, Step, Technique};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FishKind {
+    Basic,
+    // Some base candidates (the fins) lie outside the cover sets, but all in one block
+    Finned,
+    // A finned fish that would be degenerate without its fins
+    Sashimi,
+}
+
+fn technique(size: usize, kind: FishKind) -> Technique {
+    match (size, kind) {
+        (2, FishKind::Basic) => Technique::XWing,
+        (3, FishKind::Basic) => Technique::Swordfish,
+        (_, FishKind::Basic) => Technique::Jellyfish,
+        (2, FishKind::Finned) => Technique::FinnedXWing,
+        (3, FishKind::Finned) => Technique::FinnedSwordfish,
+        (_, FishKind::Finned) => Technique::FinnedJellyfish,
+        (2, FishKind::Sashimi) => Technique::SashimiXWing,
+        (3, FishKind::Sashimi) => Technique::SashimiSwordfish,
+        (_, FishKind::Sashimi) => Technique::SashimiJellyfish,
+    }
+}
+
+// A row (or column) with the index of a cell in the crossing direction
+fn line(rows: bool, i: u8) -> Region {
+    if rows {
+        Region::Row(Row(i))
+    } else {
+        Region::Col(Col(i))
+    }
+}
+
+fn cross_index(rows: bool, cell: Cell) -> u8 {
+    if rows {
+        cell.col().0
+    } else {
+        cell.row().0
+    }
+}
+
+fn line_index(rows: bool, cell: Cell) -> u8 {
+    if rows {
+        cell.row().0
+    } else {
+        cell.col().0
+    }
+}
+
+// Looks for `size` base lines (rows or columns), whose candidates for a digit all lie in `size`
+// cover lines. The digit can then be removed from the rest of the cover lines.
+pub fn fish(board: &Board, size: usize, kind: FishKind) -> Option<Step> {
+    for digit in 0..9 {
+        for &rows in &[true, false] {
+            let bases: Vec<u8> = (0..9)
+                .filter(|&i| {
+                    let places = places_for(board, line(rows, i), digit).len();
+                    match kind {
+                        FishKind::Basic => places >= 2 && places <= size,
+                        _ => places >= 1,
+                    }
+                })
+                .collect();
+            for base in combinations(&bases, size) {
+                let cells: Vec<Cell> = base
+                    .iter()
+                    .flat_map(|&i| places_for(board, line(rows, i), digit))
+                    .collect();
+                let crossing = cells.iter().fold(0u16, |mask, &cell| {
+                    mask | number_to_mask(cross_index(rows, cell))
+                });
+                let step = if kind == FishKind::Basic {
+                    if crossing.count_ones() as usize != size {
+                        continue;
+                    }
+                    fish_step(board, digit, rows, &base, crossing, &cells, kind)
+                } else {
+                    if crossing.count_ones() as usize <= size {
+                        continue;
+                    }
+                    let crossing: Vec<u8> = (0..9)
+                        .filter(|&i| crossing & number_to_mask(i) > 0)
+                        .collect();
+                    combinations(&crossing, size).into_iter().find_map(|cover| {
+                        let cover = cover.iter().fold(0u16, |mask, &i| mask | number_to_mask(i));
+                        fish_step(board, digit, rows, &base, cover, &cells, kind)
+                    })
+                };
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+fn fish_step(
+    board: &Board,
+    digit: u8,
+    rows: bool,
+    base: &[u8],
+    cover: u16,
+    cells: &[Cell],
+    kind: FishKind,
+) -> Option<Step> {
+    let fins: Vec<Cell> = cells
+        .iter()
+        .copied()
+        .filter(|&cell| cover & number_to_mask(cross_index(rows, cell)) == 0)
+        .collect();
+
+    if kind != FishKind::Basic {
+        if fins.is_empty() || fins.iter().any(|fin| fin.block() != fins[0].block()) {
+            return None;
+        }
+        // Every base line needs at least one candidate in the cover lines, if one of them has
+        // only a single one, the fish is sashimi
+        let mut sashimi = false;
+        for &i in base {
+            let covered = cells
+                .iter()
+                .filter(|&&cell| line_index(rows, cell) == i && !fins.contains(&cell))
+                .count();
+            if covered == 0 {
+                return None;
+            }
+            sashimi |= covered == 1;
+        }
+        if sashimi != (kind == FishKind::Sashimi) {
+            return None;
+        }
+    }
+
+    let cover_lines: Vec<u8> = (0..9).filter(|&i| cover & number_to_mask(i) > 0).collect();
+    let eliminations: Vec<(Cell, u8)> = cover_lines
+        .iter()
+        .flat_map(|&i| places_for(board, line(!rows, i), digit))
+        .filter(|cell| !base.contains(&line_index(rows, *cell)))
+        .filter(|&cell| fins.iter().all(|&fin| fin.sees(cell)))
+        .map(|cell| (cell, digit))
+        .collect();
+    if eliminations.is_empty() {
+        return None;
+    }
+
+    Some(Step {
+        technique: technique(base.len(), kind),
+        pattern: Pattern::Fish {
+            digit,
+            base: base.iter().map(|&i| line(rows, i)).collect(),
+            cover: cover_lines.iter().map(|&i| line(!rows, i)).collect(),
+            fins,
+        },
+        placement: None,
+        eliminations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::logic::tests::{assert_step, grid};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn x_wing() {
+        let board = grid(
+            "
+            8  9    56   | 4 1 3  | 2567 26  2567
+            37 4    17   | 2 6 5  | 9    13  8
+            2  36   156  | 7 8 9  | 356  136 4
+            -------------+--------+--------------
+            36 7    4    | 1 5 26 | 8    236 9
+            9  1    26   | 3 4 8  | 256  7   256
+            5  2368 268  | 9 7 26 | 1236 4   126
+            -------------+--------+--------------
+            67 268  2678 | 5 3 4  | 126  9   126
+            4  26   3    | 8 9 1  | 267  5   267
+            1  5    9    | 6 2 7  | 4    8   3
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::XWing,
+            "X-Wing: 3 in r2,r4 is covered by c1,c8 => r3c8<>3",
+        );
+    }
+
+    #[test]
+    fn swordfish() {
+        let board = grid(
+            "
+            4 3  17 | 156 56  57 | 9  2  8
+            6 9  5  | 2   18  78 | 4  17 3
+            2 8  17 | 3   4   9  | 5  6  17
+            --------+------------+---------
+            5 4  8  | 7   9   12 | 6  3  12
+            7 12 6  | 45  3   58 | 28 14 9
+            9 12 3  | 48  12  6  | 7  48 5
+            --------+------------+---------
+            1 5  9  | 68  67  24 | 3  78 24
+            8 6  24 | 15  57  3  | 12 9  47
+            3 7  24 | 9   128 14 | 18 5  6
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::Swordfish,
+            "Swordfish: 1 in r2,r5,r6 is covered by c2,c5,c8 => r9c5<>1",
+        );
+    }
+
+    #[test]
+    fn jellyfish() {
+        let board = grid(
+            "
+            156  3  156 | 14   7    1458 | 18   2   9
+            15   78 2   | 13   9    1358 | 4    68  167
+            9    78 4   | 6    18   2    | 3    58  157
+            ------------+----------------+-------------
+            256  9  3   | 27   4    67   | 56   1   8
+            1256 4  156 | 123  1358 1368 | 9    7   356
+            7    15 8   | 9    135  136  | 2    356 4
+            ------------+----------------+-------------
+            48   6  157 | 1347 13   1347 | 1578 9   2
+            3    15 157 | 8    2    9    | 1567 4   56
+            48   2  9   | 5    6    147  | 178  38  13
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::Jellyfish,
+            "Jellyfish: 5 in r1,r2,r4,r7 is covered by c1,c3,c6,c7 => r5c1<>5, r5c3<>5, r8c3<>5, \
+             r8c7<>5",
+        );
+    }
+
+    #[test]
+    fn finned_x_wing() {
+        let board = grid(
+            "
+            28 5    7   | 89   4  3   | 289 1   6
+            28 4    69  | 5    1  678 | 279 3   278
+            3  1    69  | 6789 2  678 | 5   78  4
+            ------------+-------------+-------------
+            6  278  5   | 4    37 9   | 1   278 2378
+            17 3    128 | 278  6  278 | 4   5   9
+            4  9    28  | 2378 5  1   | 278 6   2378
+            ------------+-------------+-------------
+            17 278  4   | 237  37 5   | 6   9   1278
+            9  267  3   | 1    8  267 | 27  4   5
+            5  2678 128 | 267  9  4   | 3   278 1278
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::FinnedXWing,
+            "Finned X-Wing: 2 in c2,c8 is covered by r4,r9 with fins in r7c2,r8c2 => r9c3<>2",
+        );
+    }
+
+    #[test]
+    fn sashimi_x_wing() {
+        let board = grid(
+            "
+            28 5   7  | 89  4  3   | 29  1  6
+            28 4   69 | 5   1  68  | 279 3  27
+            3  1   69 | 679 2  67  | 5   8  4
+            ----------+------------+-----------
+            6  8   5  | 4   37 9   | 1   27 237
+            7  3   1  | 28  6  28  | 4   5  9
+            4  9   2  | 37  5  1   | 8   6  37
+            ----------+------------+-----------
+            1  27  4  | 237 37 5   | 6   9  8
+            9  267 3  | 1   8  267 | 27  4  5
+            5  267 8  | 267 9  4   | 3   27 1
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::SashimiXWing,
+            "Sashimi X-Wing: 7 in c5,c8 is covered by r4,r9 with fins in r7c5 => r9c4<>7",
+        );
+    }
+
+    #[test]
+    fn finned_swordfish() {
+        let board = grid(
+            "
+            4 3  17 | 1568 15678 1578  | 9    1278 28
+            6 9  5  | 2    178   178   | 148  1478 3
+            2 8  17 | 3    4     9     | 5    6    17
+            --------+------------------+---------------
+            5 4  8  | 7    9     12    | 6    3    12
+            7 12 6  | 1458 3     12458 | 1248 1248 9
+            9 12 3  | 148  128   6     | 7    1248 5
+            --------+------------------+---------------
+            1 5  9  | 468  2678  2478  | 3    278  2478
+            8 6  24 | 145  1257  3     | 12   9    1247
+            3 7  24 | 9    128   1248  | 128  5    6
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::FinnedSwordfish,
+            "Finned Swordfish: 2 in r1,r4,r7 is covered by c6,c8,c9 with fins in r7c5 => r9c6<>2",
+        );
+    }
+
+    #[test]
+    fn sashimi_swordfish() {
+        let board = grid(
+            "
+            4 3  17 | 156 56  57 | 9    2   8
+            6 9  5  | 2   18  78 | 14   147 3
+            2 8  17 | 3   4   9  | 5    6   17
+            --------+------------+-------------
+            5 4  8  | 7   9   12 | 6    3   12
+            7 12 6  | 45  3   58 | 1248 148 9
+            9 12 3  | 148 128 6  | 7    48  5
+            --------+------------+-------------
+            1 5  9  | 68  67  24 | 3    78  24
+            8 6  24 | 15  57  3  | 12   9   147
+            3 7  24 | 9   128 14 | 18   5   6
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::SashimiSwordfish,
+            "Sashimi Swordfish: 1 in r1,r3,r4 is covered by c3,c4,c9 with fins in r4c6 => r6c4<>1",
+        );
+    }
+
+    #[test]
+    fn finned_jellyfish() {
+        let board = grid(
+            "
+            9 7   2  | 14   5    34   | 134  6  8
+            5 3   8  | 147  6    479  | 1249 14 249
+            6 14  14 | 2    8    39   | 35   7  359
+            ---------+----------------+------------
+            8 125 15 | 9    124  6    | 7    3  245
+            3 6   7  | 45   124  8    | 1245 9  245
+            4 125 9  | 3    127  257  | 1258 18 6
+            ---------+----------------+------------
+            2 8   36 | 46   49   1    | 349  5  7
+            1 45  36 | 4567 2479 2457 | 3489 48 349
+            7 9   45 | 8    3    45   | 6    2  1
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::FinnedJellyfish,
+            "Finned Jellyfish: 4 in r1,r4,r5,r7 is covered by c4,c5,c7,c9 with fins in r1c6 => \
+             r2c4<>4",
+        );
+    }
+
+    #[test]
+    fn sashimi_jellyfish() {
+        let board = grid(
+            "
+            15  7  6    | 124 2459 459  | 138 1234 148
+            158 28 124  | 124 3    145  | 7   6    9
+            9   3  124  | 8   6    7    | 5   124  14
+            ------------+---------------+-------------
+            137 19 139  | 47  48   2    | 18  5    6
+            4   5  8    | 9   1    6    | 2   7    3
+            2   6  17   | 5   78   3    | 9   14   148
+            ------------+---------------+-------------
+            37  19 5    | 67  49   149  | 36  8    2
+            6   28 1279 | 3   279  189  | 4   19   5
+            138 4  1239 | 126 259  1589 | 136 139  7
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::SashimiJellyfish,
+            "Sashimi Jellyfish: 1 in r3,r6,r7,r8 is covered by c3,c6,c8,c9 with fins in r7c2 => \
+             r9c3<>1",
+        );
+    }
+}
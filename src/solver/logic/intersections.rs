@@ -0,0 +1,146 @@
+use crate::solver::board::Board;
+use crate::solver::calc::{Block, Cell, Col, House, Region, Row};
+use crate::solver::logic::{places_for, Pattern, Step, Technique};
+
+// Builds the step for a digit that is confined to the intersection of base and cover, removing
+// it from the rest of the cover region
+fn intersection(
+    board: &Board,
+    technique: Technique,
+    digit: u8,
+    cells: Vec<Cell>,
+    base: Region,
+    cover: Region,
+) -> Option<Step> {
+    let eliminations: Vec<(Cell, u8)> = places_for(board, cover, digit)
+        .into_iter()
+        .filter(|cell| !cells.contains(cell))
+        .map(|cell| (cell, digit))
+        .collect();
+    if eliminations.is_empty() {
+        return None;
+    }
+    Some(Step {
+        technique,
+        pattern: Pattern::Intersection {
+            digit,
+            cells,
+            base,
+            cover,
+        },
+        placement: None,
+        eliminations,
+    })
+}
+
+pub fn pointing(board: &Board) -> Option<Step> {
+    for block in 0..9 {
+        let base = Region::Block(Block(block));
+        for digit in 0..9 {
+            let cells = places_for(board, base, digit);
+            if cells.len() < 2 {
+                continue;
+            }
+            let covers = [Region::Row(cells[0].row()), Region::Col(cells[0].col())];
+            for &cover in &covers {
+                if cells.iter().all(|&cell| cover.contains(cell)) {
+                    let step = intersection(
+                        board,
+                        Technique::PointingPair,
+                        digit,
+                        cells.clone(),
+                        base,
+                        cover,
+                    );
+                    if step.is_some() {
+                        return step;
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+pub fn box_line_reduction(board: &Board) -> Option<Step> {
+    let lines = (0..9)
+        .map(|i| Region::Row(Row(i)))
+        .chain((0..9).map(|i| Region::Col(Col(i))));
+    for base in lines {
+        for digit in 0..9 {
+            let cells = places_for(board, base, digit);
+            if cells.len() < 2 {
+                continue;
+            }
+            let block = cells[0].block();
+            if cells.iter().all(|cell| cell.block() == block) {
+                let step = intersection(
+                    board,
+                    Technique::BoxLineReduction,
+                    digit,
+                    cells,
+                    base,
+                    Region::Block(block),
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::logic::tests::{assert_step, grid};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn pointing_pair() {
+        let board = grid(
+            "
+            8    7     25    | 245   14   6     | 125  9    3
+            3    12569 2569  | 2579  8    12579 | 1256 4    67
+            1269 12569 4     | 23579 13   12579 | 1256 8    67
+            -----------------+------------------+-------------
+            267  2568  25678 | 24    9    24    | 36   36   1
+            4    26    26    | 1     5    3     | 8    7    9
+            19   19    3     | 6     7    8     | 4    2    5
+            -----------------+------------------+-------------
+            69   4     1     | 8     36   59    | 7    356  2
+            5    236   267   | 347   1346 147   | 9    136  8
+            679  3689  6789  | 3579  2    1579  | 1356 1356 4
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::PointingPair,
+            "Pointing Pair: 1 in b3 is confined to c7 (r1c7,r2c7,r3c7) => r9c7<>1",
+        );
+    }
+
+    #[test]
+    fn box_line_reduction() {
+        let board = grid(
+            "
+            38   4   138 | 2    9    17   | 37  5  6
+            36   9   26  | 347  347  5    | 237 8  1
+            5    12  7   | 13   6    8    | 23  9  4
+            -------------+----------------+---------
+            3478 6   138 | 1347 2347 1247 | 5   37 9
+            347  5   39  | 3479 8    479  | 6   1  2
+            2    17  139 | 6    5    179  | 4   37 8
+            -------------+----------------+---------
+            1    278 26  | 478  247  3    | 9   46 5
+            678  278 5   | 4789 247  2479 | 1   46 3
+            9    3   4   | 5    1    6    | 8   2  7
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::BoxLineReduction,
+            "Box/Line Reduction: 8 in c2 is confined to b7 (r7c2,r8c2) => r8c1<>8",
+        );
+    }
+}
@@ -0,0 +1,709 @@
+use crate::errors::{ContradicoryAssignmentError, PuzzleError};
+use crate::solver::board::Board;
+use crate::solver::calc::{mask_to_numbers, number_to_mask, Cell, House, Region};
+use als::Als;
+use fish::FishKind;
+use std::fmt;
+
+mod als;
+mod chains;
+mod fish;
+mod intersections;
+mod singles;
+mod subsets;
+mod uniqueness;
+mod wings;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Technique {
+    HiddenSingle,
+    NakedSingle,
+    PointingPair,
+    BoxLineReduction,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    NakedQuad,
+    HiddenQuad,
+    XWing,
+    Swordfish,
+    Jellyfish,
+    FinnedXWing,
+    SashimiXWing,
+    FinnedSwordfish,
+    SashimiSwordfish,
+    FinnedJellyfish,
+    SashimiJellyfish,
+    XYWing,
+    XYZWing,
+    WWing,
+    SimpleColoring,
+    XChain,
+    XYChain,
+    Aic,
+    UniqueRectangle1,
+    UniqueRectangle2,
+    UniqueRectangle3,
+    UniqueRectangle4,
+    UniqueRectangle5,
+    UniqueRectangle6,
+    HiddenRectangle,
+    BugPlusOne,
+    AlsXz,
+    AlsXyWing,
+    DeathBlossom,
+}
+
+impl Technique {
+    // All techniques, from the easiest to the hardest. Sorted by rating, so the logical solver
+    // always picks the cheapest step.
+    pub const ALL: [Technique; 37] = [
+        Technique::HiddenSingle,
+        Technique::NakedSingle,
+        Technique::PointingPair,
+        Technique::BoxLineReduction,
+        Technique::NakedPair,
+        Technique::XWing,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::Swordfish,
+        Technique::HiddenTriple,
+        Technique::XYWing,
+        Technique::XYZWing,
+        Technique::WWing,
+        Technique::SimpleColoring,
+        Technique::UniqueRectangle1,
+        Technique::UniqueRectangle2,
+        Technique::UniqueRectangle4,
+        Technique::UniqueRectangle3,
+        Technique::UniqueRectangle5,
+        Technique::UniqueRectangle6,
+        Technique::HiddenRectangle,
+        Technique::NakedQuad,
+        Technique::Jellyfish,
+        Technique::HiddenQuad,
+        Technique::BugPlusOne,
+        Technique::FinnedXWing,
+        Technique::SashimiXWing,
+        Technique::FinnedSwordfish,
+        Technique::SashimiSwordfish,
+        Technique::FinnedJellyfish,
+        Technique::SashimiJellyfish,
+        Technique::XChain,
+        Technique::XYChain,
+        Technique::AlsXz,
+        Technique::AlsXyWing,
+        Technique::DeathBlossom,
+        Technique::Aic,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::NakedSingle => "Naked Single",
+            Technique::PointingPair => "Pointing Pair",
+            Technique::BoxLineReduction => "Box/Line Reduction",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::FinnedJellyfish => "Finned Jellyfish",
+            Technique::SashimiJellyfish => "Sashimi Jellyfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::SimpleColoring => "Simple Coloring",
+            Technique::XChain => "X-Chain",
+            Technique::XYChain => "XY-Chain",
+            Technique::Aic => "Alternating Inference Chain",
+            Technique::UniqueRectangle1 => "Unique Rectangle Type 1",
+            Technique::UniqueRectangle2 => "Unique Rectangle Type 2",
+            Technique::UniqueRectangle3 => "Unique Rectangle Type 3",
+            Technique::UniqueRectangle4 => "Unique Rectangle Type 4",
+            Technique::UniqueRectangle5 => "Unique Rectangle Type 5",
+            Technique::UniqueRectangle6 => "Unique Rectangle Type 6",
+            Technique::HiddenRectangle => "Hidden Unique Rectangle",
+            Technique::BugPlusOne => "BUG+1",
+            Technique::AlsXz => "ALS-XZ",
+            Technique::AlsXyWing => "ALS-XY-Wing",
+            Technique::DeathBlossom => "Death Blossom",
+        }
+    }
+
+    // The difficulty of the technique on the scale of Sudoku Explainer. Techniques it doesn't
+    // know are placed next to the ones of comparable effort.
+    pub fn rating(self) -> f32 {
+        match self {
+            Technique::HiddenSingle => 1.5,
+            Technique::NakedSingle => 2.3,
+            Technique::PointingPair => 2.6,
+            Technique::BoxLineReduction => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::XWing => 3.2,
+            Technique::HiddenPair => 3.4,
+            Technique::NakedTriple => 3.6,
+            Technique::Swordfish => 3.8,
+            Technique::HiddenTriple => 4.0,
+            Technique::XYWing => 4.2,
+            Technique::XYZWing => 4.4,
+            Technique::WWing => 4.4,
+            Technique::SimpleColoring => 4.5,
+            Technique::UniqueRectangle1 => 4.5,
+            Technique::UniqueRectangle2 => 4.5,
+            Technique::UniqueRectangle4 => 4.5,
+            Technique::UniqueRectangle3 => 4.6,
+            Technique::UniqueRectangle5 => 4.6,
+            Technique::UniqueRectangle6 => 4.6,
+            Technique::HiddenRectangle => 4.6,
+            Technique::NakedQuad => 5.0,
+            Technique::Jellyfish => 5.2,
+            Technique::HiddenQuad => 5.4,
+            Technique::BugPlusOne => 5.6,
+            Technique::FinnedXWing => 5.7,
+            Technique::SashimiXWing => 5.8,
+            Technique::FinnedSwordfish => 5.9,
+            Technique::SashimiSwordfish => 6.0,
+            Technique::FinnedJellyfish => 6.1,
+            Technique::SashimiJellyfish => 6.2,
+            Technique::XChain => 6.5,
+            Technique::XYChain => 6.6,
+            Technique::AlsXz => 6.8,
+            Technique::AlsXyWing => 7.0,
+            Technique::DeathBlossom => 7.2,
+            Technique::Aic => 7.5,
+        }
+    }
+
+    // Techniques that are only valid if the puzzle is known to have a single solution
+    pub fn requires_uniqueness(self) -> bool {
+        matches!(
+            self,
+            Technique::UniqueRectangle1
+                | Technique::UniqueRectangle2
+                | Technique::UniqueRectangle3
+                | Technique::UniqueRectangle4
+                | Technique::UniqueRectangle5
+                | Technique::UniqueRectangle6
+                | Technique::HiddenRectangle
+                | Technique::BugPlusOne
+        )
+    }
+
+    // Looks for the first application of this technique on the board
+    pub fn find(self, board: &Board) -> Option<Step> {
+        match self {
+            Technique::HiddenSingle => singles::hidden_single(board),
+            Technique::NakedSingle => singles::naked_single(board),
+            Technique::PointingPair => intersections::pointing(board),
+            Technique::BoxLineReduction => intersections::box_line_reduction(board),
+            Technique::NakedPair => subsets::naked_subset(board, 2),
+            Technique::HiddenPair => subsets::hidden_subset(board, 2),
+            Technique::NakedTriple => subsets::naked_subset(board, 3),
+            Technique::HiddenTriple => subsets::hidden_subset(board, 3),
+            Technique::NakedQuad => subsets::naked_subset(board, 4),
+            Technique::HiddenQuad => subsets::hidden_subset(board, 4),
+            Technique::XWing => fish::fish(board, 2, FishKind::Basic),
+            Technique::Swordfish => fish::fish(board, 3, FishKind::Basic),
+            Technique::Jellyfish => fish::fish(board, 4, FishKind::Basic),
+            Technique::FinnedXWing => fish::fish(board, 2, FishKind::Finned),
+            Technique::SashimiXWing => fish::fish(board, 2, FishKind::Sashimi),
+            Technique::FinnedSwordfish => fish::fish(board, 3, FishKind::Finned),
+            Technique::SashimiSwordfish => fish::fish(board, 3, FishKind::Sashimi),
+            Technique::FinnedJellyfish => fish::fish(board, 4, FishKind::Finned),
+            Technique::SashimiJellyfish => fish::fish(board, 4, FishKind::Sashimi),
+            Technique::XYWing => wings::xy_wing(board),
+            Technique::XYZWing => wings::xyz_wing(board),
+            Technique::WWing => wings::w_wing(board),
+            Technique::SimpleColoring => chains::simple_coloring(board),
+            Technique::XChain => chains::x_chain(board),
+            Technique::XYChain => chains::xy_chain(board),
+            Technique::Aic => chains::aic(board),
+            Technique::UniqueRectangle1 => uniqueness::type_1(board),
+            Technique::UniqueRectangle2 => uniqueness::type_2(board),
+            Technique::UniqueRectangle3 => uniqueness::type_3(board),
+            Technique::UniqueRectangle4 => uniqueness::type_4(board),
+            Technique::UniqueRectangle5 => uniqueness::type_5(board),
+            Technique::UniqueRectangle6 => uniqueness::type_6(board),
+            Technique::HiddenRectangle => uniqueness::hidden_rectangle(board),
+            Technique::BugPlusOne => uniqueness::bug_plus_one(board),
+            Technique::AlsXz => als::als_xz(board),
+            Technique::AlsXyWing => als::als_xy_wing(board),
+            Technique::DeathBlossom => als::death_blossom(board),
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// The cells (and houses) that make up a deduction
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Pattern {
+    // A cell that can only hold one value. If a region is given, the value has no other place
+    // left in that region.
+    Single {
+        cell: Cell,
+        digit: u8,
+        region: Option<Region>,
+    },
+    // Cells in a region that are limited to as many digits as there are cells
+    Subset {
+        region: Region,
+        cells: Vec<Cell>,
+        digits: u16,
+    },
+    // All places for a digit in the base region are also in the cover region
+    Intersection {
+        digit: u8,
+        cells: Vec<Cell>,
+        base: Region,
+        cover: Region,
+    },
+    // All candidates for a digit in the base lines lie in the cover lines, except for the fins
+    Fish {
+        digit: u8,
+        base: Vec<Region>,
+        cover: Vec<Region>,
+        fins: Vec<Cell>,
+    },
+    // Pincers that have to contain the digit, no matter what the pivot holds. For a W-Wing, the
+    // pivot is the strong link connecting the pincers.
+    Wing {
+        pivot: Vec<Cell>,
+        pincers: Vec<Cell>,
+        digit: u8,
+    },
+    // Candidates of a digit connected by strong links, in two alternating colors
+    Coloring {
+        digit: u8,
+        on: Vec<Cell>,
+        off: Vec<Cell>,
+    },
+    // Candidates alternately connected by strong and weak links, starting and ending with a
+    // strong one
+    Chain {
+        nodes: Vec<(Cell, u8)>,
+    },
+    // Four corners that would allow swapping the two digits, if they were the only candidates
+    Rectangle {
+        cells: Vec<Cell>,
+        digits: u16,
+    },
+    // The only cell with three candidates, all others have two
+    Bug {
+        cell: Cell,
+        digit: u8,
+    },
+    // Almost locked sets, connected by restricted common candidates (or a stem cell)
+    Als {
+        stem: Option<Cell>,
+        sets: Vec<Als>,
+        restricted: u16,
+    },
+}
+
+impl Pattern {
+    // The houses to look at to find the pattern. Patterns that aren't bound to houses point to the
+    // blocks their cells lie in.
+    pub fn regions(&self) -> Vec<Region> {
+        let cells: Vec<Cell> = match self {
+            Pattern::Single {
+                region: Some(region),
+                ..
+            } => return vec![*region],
+            Pattern::Subset { region, .. } => return vec![*region],
+            Pattern::Intersection { base, cover, .. } => return vec![*base, *cover],
+            Pattern::Fish { base, cover, .. } => {
+                return base.iter().chain(cover).copied().collect()
+            }
+            Pattern::Als {
+                stem: None, sets, ..
+            } => return sets.iter().map(|als| als.house).collect(),
+            Pattern::Single { cell, .. } | Pattern::Bug { cell, .. } => vec![*cell],
+            Pattern::Wing { pivot, pincers, .. } => pivot.iter().chain(pincers).copied().collect(),
+            Pattern::Coloring { on, off, .. } => on.iter().chain(off).copied().collect(),
+            Pattern::Chain { nodes } => nodes.iter().map(|&(cell, _)| cell).collect(),
+            Pattern::Rectangle { cells, .. } => cells.clone(),
+            Pattern::Als {
+                stem: Some(stem),
+                sets,
+                ..
+            } => sets
+                .iter()
+                .flat_map(|als| als.cells.iter().copied())
+                .chain(Some(*stem))
+                .collect(),
+        };
+        let mut blocks: Vec<Region> = vec![];
+        for cell in cells {
+            if !blocks.contains(&Region::Block(cell.block())) {
+                blocks.push(Region::Block(cell.block()));
+            }
+        }
+        blocks
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pattern::Single {
+                cell,
+                digit,
+                region: Some(region),
+            } => write!(
+                f,
+                "{} is the only place for {} in {}",
+                cell.name(),
+                digit + 1,
+                region
+            ),
+            Pattern::Single { cell, digit, .. } => {
+                write!(f, "{} can only be {}", cell.name(), digit + 1)
+            }
+            Pattern::Subset {
+                region,
+                cells,
+                digits,
+            } => write!(
+                f,
+                "{} in {} ({})",
+                format_digits(*digits),
+                format_cells(cells),
+                region
+            ),
+            Pattern::Intersection {
+                digit,
+                cells,
+                base,
+                cover,
+            } => write!(
+                f,
+                "{} in {} is confined to {} ({})",
+                digit + 1,
+                base,
+                cover,
+                format_cells(cells)
+            ),
+            Pattern::Fish {
+                digit,
+                base,
+                cover,
+                fins,
+            } => {
+                write!(
+                    f,
+                    "{} in {} is covered by {}",
+                    digit + 1,
+                    format_regions(base),
+                    format_regions(cover)
+                )?;
+                if !fins.is_empty() {
+                    write!(f, " with fins in {}", format_cells(fins))?;
+                }
+                Ok(())
+            }
+            Pattern::Wing {
+                pivot,
+                pincers,
+                digit,
+            } => write!(
+                f,
+                "pivot {} with pincers {} on {}",
+                format_cells(pivot),
+                format_cells(pincers),
+                digit + 1
+            ),
+            Pattern::Coloring { digit, on, off } => write!(
+                f,
+                "{} colored {} / {}",
+                digit + 1,
+                format_cells(on),
+                format_cells(off)
+            ),
+            Pattern::Chain { nodes } => {
+                for (i, (cell, digit)) in nodes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{}", if i % 2 == 1 { "=" } else { "-" })?;
+                    }
+                    write!(f, "({}){}", digit + 1, cell.name())?;
+                }
+                Ok(())
+            }
+            Pattern::Rectangle { cells, digits } => write!(
+                f,
+                "{} in {} must not form a deadly pattern",
+                format_digits(*digits),
+                format_cells(cells)
+            ),
+            Pattern::Bug { cell, digit } => write!(
+                f,
+                "{} is the only cell with three candidates, and {} appears three times in its houses",
+                cell.name(),
+                digit + 1
+            ),
+            Pattern::Als {
+                stem,
+                sets,
+                restricted,
+            } => {
+                if let Some(stem) = stem {
+                    write!(f, "stem {} {} with petals ", stem.name(), format_digits(*restricted))?;
+                }
+                let sets: Vec<String> = sets.iter().map(|als| als.to_string()).collect();
+                write!(f, "{}", sets.join(" / "))?;
+                if stem.is_none() {
+                    write!(f, " linked by {}", format_digits(*restricted))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// A single deduction, with everything needed to explain and apply it
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Step {
+    pub technique: Technique,
+    pub pattern: Pattern,
+    pub placement: Option<(Cell, u8)>,
+    pub eliminations: Vec<(Cell, u8)>,
+}
+
+impl Step {
+    // The difficulty of this particular step. Hidden singles in blocks are easier to spot than in
+    // lines, and long chains are harder to follow than short ones.
+    pub fn rating(&self) -> f32 {
+        match &self.pattern {
+            Pattern::Single {
+                region: Some(Region::Block(_)),
+                ..
+            } => 1.2,
+            Pattern::Chain { nodes } => self.technique.rating() + length_penalty(nodes.len()),
+            _ => self.technique.rating(),
+        }
+    }
+
+    pub fn apply(&self, board: &mut Board) -> Result<(), ContradicoryAssignmentError> {
+        if let Some((cell, val)) = self.placement {
+            board.mark(cell, val)?;
+        }
+        for &(cell, val) in &self.eliminations {
+            board.eliminate(cell, val)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} =>", self.technique, self.pattern)?;
+        if let Some((cell, val)) = self.placement {
+            write!(f, " {}={}", cell.name(), val + 1)?;
+        }
+        if !self.eliminations.is_empty() {
+            let eliminations: Vec<String> = self
+                .eliminations
+                .iter()
+                .map(|(cell, val)| format!("{}<>{}", cell.name(), val + 1))
+                .collect();
+            write!(f, " {}", eliminations.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// Sudoku Explainer adds 0.1 for every time the chain outgrows a bound of 4, 6, 8, 12, 16, 24, ...
+fn length_penalty(length: usize) -> f32 {
+    let mut penalty = 0.0;
+    let mut bound = 4;
+    let mut odd = false;
+    while length > bound {
+        penalty += 0.1;
+        bound = if odd { bound * 4 / 3 } else { bound * 3 / 2 };
+        odd = !odd;
+    }
+    penalty
+}
+
+// The result of solving a board with logic alone
+pub struct Explanation {
+    pub steps: Vec<Step>,
+    // The board after the last step. It is unsolved if the logic got stuck.
+    pub board: Board,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct LogicOptions {
+    // Allows techniques that rely on the puzzle having only one solution
+    pub assume_unique: bool,
+}
+
+impl LogicOptions {
+    pub fn allows(&self, technique: Technique) -> bool {
+        self.assume_unique || !technique.requires_uniqueness()
+    }
+}
+
+// Finds the easiest step that makes progress on the board
+pub fn next_step(board: &Board, options: &LogicOptions) -> Option<Step> {
+    Technique::ALL
+        .iter()
+        .filter(|&&technique| options.allows(technique))
+        .find_map(|technique| technique.find(board))
+}
+
+pub fn solve_logically(
+    mut board: Board,
+    options: &LogicOptions,
+) -> Result<Explanation, PuzzleError> {
+    let mut steps = vec![];
+    while !board.is_solved() {
+        match next_step(&board, options) {
+            Some(step) => {
+                // Steps only go wrong on puzzles without a solution
+                step.apply(&mut board)
+                    .map_err(|_| PuzzleError::Unsolvable)?;
+                steps.push(step);
+            }
+            None => break,
+        }
+    }
+    Ok(Explanation { steps, board })
+}
+
+pub fn format_cells(cells: &[Cell]) -> String {
+    cells
+        .iter()
+        .map(|cell| cell.name())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn format_regions(regions: &[Region]) -> String {
+    regions
+        .iter()
+        .map(|region| region.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn format_digits(mask: u16) -> String {
+    let digits: String = mask_to_numbers(mask)
+        .map(|x| format!("{}", x + 1))
+        .collect();
+    format!("{{{}}}", digits)
+}
+
+// Every way to pick `k` items from the given ones, keeping their order
+pub fn combinations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    if items.len() < k {
+        return vec![];
+    }
+    let mut ret = vec![];
+    for (i, &first) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], k - 1) {
+            rest.insert(0, first);
+            ret.push(rest);
+        }
+    }
+    ret
+}
+
+// The unsolved cells of a house that can still hold the digit
+pub fn places_for<H: House>(board: &Board, house: H, digit: u8) -> Vec<Cell> {
+    house
+        .into_iter()
+        .filter(|&cell| board.candidates(cell) & number_to_mask(digit) > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::board::{CellState, Possibilities};
+    use crate::solver::solve::solve;
+
+    // Reads a grid of candidates, a cell with a single digit is solved. The lines between the
+    // blocks are only there for the eye.
+    pub fn grid(text: &str) -> Board {
+        let mut board = Board::new();
+        let cells = text
+            .split_whitespace()
+            .filter(|token| token.bytes().all(|x| x.is_ascii_digit()));
+        for (i, token) in cells.enumerate() {
+            let digits: Vec<u8> = token.bytes().map(|x| x - b'1').collect();
+            board.state[i] = match digits[..] {
+                [digit] => CellState::Solved(digit),
+                _ => CellState::Unsolved(Possibilities {
+                    mask: digits.iter().fold(0, |mask, &x| mask | number_to_mask(x)),
+                    count: digits.len() as u8,
+                }),
+            };
+        }
+        board
+    }
+
+    pub fn puzzle(text: &str) -> Board {
+        Board::from_puzzle(text.bytes().map(|x| x - b'0').collect()).unwrap()
+    }
+
+    // The next step has to be made with the technique, and read as expected
+    pub fn assert_step(board: &Board, technique: Technique, expected: &str) {
+        let options = LogicOptions {
+            assume_unique: true,
+        };
+        let step = next_step(board, &options).expect("No step found");
+        assert_eq!(step.technique, technique, "{}", step);
+        assert_eq!(step.to_string(), expected);
+    }
+
+    // Checks every step of the logical solution against the actual solution
+    fn assert_sound(text: &str) {
+        let board = puzzle(text);
+        let solution = solve(board.clone(), true).unwrap().unwrap();
+        let options = LogicOptions {
+            assume_unique: true,
+        };
+        let explanation = solve_logically(board, &options).unwrap();
+        for step in &explanation.steps {
+            if let Some((cell, digit)) = step.placement {
+                assert!(solution[cell] == CellState::Solved(digit), "{}", step);
+            }
+            for &(cell, digit) in &step.eliminations {
+                assert!(solution[cell] != CellState::Solved(digit), "{}", step);
+            }
+        }
+    }
+
+    #[test]
+    fn sound_on_hard_puzzles() {
+        // Between them they need most of the techniques, the last one gets stuck after 22 steps
+        for text in &[
+            "000000000695200003080040500040700000706000009003006705150000000000003090300900006",
+            "006700040005000002090100050001040068000029004000000700050000000067210000300060500",
+            "076000000000000069930807000000002000008016070200503900005000082000300405040000000",
+            "201030060000004200307069140450700000602081000000002300800000007064008020000000000",
+            "970050008530060000000200000000900730000008090000300006080001057100000000790030620",
+            "905008023070200801000063000000082070200000050010070004400005000090600005001040000",
+        ] {
+            assert_sound(text);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::solver::board::Board;
+use crate::solver::board::CellState::Unsolved;
+use crate::solver::calc::{mask_to_numbers, Cell, Region};
+use crate::solver::logic::{places_for, Pattern, Step, Technique};
+
+pub fn naked_single(board: &Board) -> Option<Step> {
+    (0..81).map(Cell).find_map(|cell| match board[cell] {
+        Unsolved(possibilities) if possibilities.count == 1 => {
+            let digit = mask_to_numbers(possibilities.mask).next()?;
+            Some(Step {
+                technique: Technique::NakedSingle,
+                pattern: Pattern::Single {
+                    cell,
+                    digit,
+                    region: None,
+                },
+                placement: Some((cell, digit)),
+                eliminations: vec![],
+            })
+        }
+        _ => None,
+    })
+}
+
+pub fn hidden_single(board: &Board) -> Option<Step> {
+    for region in Region::all() {
+        for digit in 0..9 {
+            if let [cell] = places_for(board, region, digit)[..] {
+                return Some(Step {
+                    technique: Technique::HiddenSingle,
+                    pattern: Pattern::Single {
+                        cell,
+                        digit,
+                        region: Some(region),
+                    },
+                    placement: Some((cell, digit)),
+                    eliminations: vec![],
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::logic::tests::{assert_step, puzzle};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn hidden_single() {
+        let board = puzzle(
+            "705309640009500007000600000010000076400100000500020900000000350000807120050030000",
+        );
+        assert_step(
+            &board,
+            Technique::HiddenSingle,
+            "Hidden Single: r3c5 is the only place for 7 in b2 => r3c5=7",
+        );
+    }
+
+    #[test]
+    fn naked_single() {
+        let board = puzzle(
+            "705319640009540007000670090010985476497163000500724900070096350000857120050031760",
+        );
+        assert_step(
+            &board,
+            Technique::NakedSingle,
+            "Naked Single: r5c8 can only be 8 => r5c8=8",
+        );
+    }
+}
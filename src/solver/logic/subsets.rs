@@ -0,0 +1,256 @@
+use crate::solver::board::Board;
+use crate::solver::calc::{mask_to_numbers, number_to_mask, Cell, Region};
+use crate::solver::logic::{combinations, places_for, Pattern, Step, Technique};
+
+fn naked_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::NakedPair,
+        3 => Technique::NakedTriple,
+        _ => Technique::NakedQuad,
+    }
+}
+
+fn hidden_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::HiddenPair,
+        3 => Technique::HiddenTriple,
+        _ => Technique::HiddenQuad,
+    }
+}
+
+// `size` cells in a region that only have `size` digits between them. These digits can't go
+// anywhere else in the region.
+pub fn naked_subset(board: &Board, size: usize) -> Option<Step> {
+    for region in Region::all() {
+        let unsolved: Vec<Cell> = region
+            .into_iter()
+            .filter(|&cell| board.candidates(cell) != 0)
+            .collect();
+        let possible: Vec<Cell> = unsolved
+            .iter()
+            .copied()
+            .filter(|&cell| board.candidates(cell).count_ones() as usize <= size)
+            .collect();
+        for cells in combinations(&possible, size) {
+            let digits = cells
+                .iter()
+                .fold(0u16, |mask, &cell| mask | board.candidates(cell));
+            if digits.count_ones() as usize != size {
+                continue;
+            }
+            let eliminations: Vec<(Cell, u8)> = unsolved
+                .iter()
+                .filter(|cell| !cells.contains(cell))
+                .flat_map(|&cell| {
+                    mask_to_numbers(board.candidates(cell) & digits).map(move |digit| (cell, digit))
+                })
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: naked_technique(size),
+                    pattern: Pattern::Subset {
+                        region,
+                        cells,
+                        digits,
+                    },
+                    placement: None,
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+// `size` digits that only have `size` cells left in a region. These cells can't hold any other
+// digit.
+pub fn hidden_subset(board: &Board, size: usize) -> Option<Step> {
+    for region in Region::all() {
+        let possible: Vec<u8> = (0..9)
+            .filter(|&digit| {
+                let places = places_for(board, region, digit).len();
+                places >= 2 && places <= size
+            })
+            .collect();
+        for digits in combinations(&possible, size) {
+            let mut cells: Vec<Cell> = digits
+                .iter()
+                .flat_map(|&digit| places_for(board, region, digit))
+                .collect();
+            cells.sort();
+            cells.dedup();
+            if cells.len() != size {
+                continue;
+            }
+            let mask = digits
+                .iter()
+                .fold(0u16, |mask, &digit| mask | number_to_mask(digit));
+            let eliminations: Vec<(Cell, u8)> = cells
+                .iter()
+                .flat_map(|&cell| {
+                    mask_to_numbers(board.candidates(cell) & !mask).map(move |digit| (cell, digit))
+                })
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: hidden_technique(size),
+                    pattern: Pattern::Subset {
+                        region,
+                        cells,
+                        digits: mask,
+                    },
+                    placement: None,
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::board::Board;
+    use crate::solver::calc::Cell;
+    use crate::solver::logic::tests::{assert_step, grid};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn naked_pair() {
+        let board = grid(
+            "
+            9    4   5   | 17   167    167  | 2   3   8
+            68   18  16  | 4    23     23   | 5   7   9
+            3    7   2   | 5    8      9    | 14  14  6
+            -------------+------------------+------------
+            47   35  8   | 9    1457   157  | 134 6   2
+            2467 9   467 | 1237 124567 8    | 134 145 157
+            1    235 467 | 237  24567  2567 | 8   9   57
+            -------------+------------------+------------
+            478  18  147 | 6    357    357  | 9   2   15
+            27   12  9   | 8    57     4    | 6   15  3
+            5    6   3   | 12   9      12   | 7   8   4
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::NakedPair,
+            "Naked Pair: {18} in r2c2,r7c2 (c2) => r8c2<>1",
+        );
+    }
+
+    #[test]
+    fn hidden_pair() {
+        let board = grid(
+            "
+            68  468 279 | 1  789 5  | 247  3  279
+            5   13  79  | 39 2   4  | 17   8  6
+            138 48  279 | 6  789 37 | 1247 5  1279
+            ------------+-----------+-------------
+            4   9   1   | 2  3   6  | 5    7  8
+            2   5   6   | 8  17  17 | 9    4  3
+            37  37  8   | 5  4   9  | 126  16 12
+            ------------+-----------+-------------
+            169 2   5   | 7  19  8  | 3    16 4
+            179 17  4   | 39 6   13 | 8    2  5
+            168 68  3   | 4  5   2  | 167  9  17
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::HiddenPair,
+            "Hidden Pair: {13} in r2c2,r3c1 (b1) => r3c1<>8",
+        );
+    }
+
+    #[test]
+    fn naked_triple() {
+        let board = grid(
+            "
+            9  25  1   | 4  3  7   | 6  8   25
+            4  56  7   | 58 89 26  | 3  1   259
+            28 356 238 | 15 19 26  | 59 7   4
+            -----------+-----------+-----------
+            6  1   58  | 37 78 39  | 2  4   59
+            3  7   9   | 2  4  5   | 8  6   1
+            28 4   258 | 18 6  19  | 7  59  3
+            -----------+-----------+-----------
+            1  8   23  | 6  5  34  | 49 239 7
+            57 23  6   | 9  17 134 | 45 235 8
+            57 9   4   | 37 2  8   | 1  35  6
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::NakedTriple,
+            "Naked Triple: {159} in r3c4,r3c5,r3c7 (r3) => r3c2<>5",
+        );
+    }
+
+    #[test]
+    fn hidden_triple() {
+        let board = grid(
+            "
+            1   26  5  | 4   26 7  | 3 9  8
+            38  278 89 | 123 58 19 | 6 57 4
+            346 678 49 | 36  58 69 | 1 2  57
+            -----------+-----------+--------
+            68  68  1  | 7   4  2  | 5 3  9
+            9   4   2  | 16  3  5  | 7 8  16
+            5   3   7  | 8   9  16 | 2 4  16
+            -----------+-----------+--------
+            48  1   48 | 5   26 3  | 9 67 27
+            2   5   6  | 9   7  8  | 4 1  3
+            7   9   3  | 26  1  4  | 8 56 25
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::HiddenTriple,
+            "Hidden Triple: {578} in r3c2,r3c5,r3c9 (r3) => r3c2<>6",
+        );
+    }
+
+    #[test]
+    fn naked_quad() {
+        let board = grid(
+            "
+            58  4  358 | 78  2    37  | 1 6   9
+            1   6  38  | 4   89   39  | 5 7   2
+            9   2  7   | 6   1    5   | 8 3   4
+            -----------+--------------+----------
+            256 19 159 | 23  3679 8   | 4 259 57
+            28  3  4   | 5   79   279 | 6 129 178
+            268 7  589 | 1   4    269 | 3 259 58
+            -----------+--------------+----------
+            3   19 6   | 79  57   4   | 2 8   15
+            4   8  29  | 239 35   12  | 7 15  6
+            7   5  12  | 28  68   16  | 9 4   3
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::NakedQuad,
+            "Naked Quad: {1268} in r8c6,r9c4,r9c5,r9c6 (b8) => r8c4<>2",
+        );
+    }
+
+    // A house with a solved cell always shows the naked subset of its other cells first, so the
+    // quad needs an empty house
+    #[test]
+    fn hidden_quad() {
+        let mut board = Board::new();
+        for cell in 4..9 {
+            for digit in 0..4 {
+                board.eliminate(Cell(cell), digit).unwrap();
+            }
+        }
+        assert_step(
+            &board,
+            Technique::HiddenQuad,
+            "Hidden Quad: {1234} in r1c1,r1c2,r1c3,r1c4 (r1) => r1c1<>5, r1c1<>6, r1c1<>7, \
+             r1c1<>8, r1c1<>9, r1c2<>5, r1c2<>6, r1c2<>7, r1c2<>8, r1c2<>9, r1c3<>5, r1c3<>6, \
+             r1c3<>7, r1c3<>8, r1c3<>9, r1c4<>5, r1c4<>6, r1c4<>7, r1c4<>8, r1c4<>9",
+        );
+    }
+}
@@ -0,0 +1,520 @@
+use crate::solver::board::Board;
+use crate::solver::calc::{mask_to_numbers, number_to_mask, Cell, House, Region};
+use crate::solver::logic::{combinations, places_for, Pattern, Step, Technique};
+
+// Four unsolved cells in two rows, two columns and two blocks, that all still contain the same two
+// digits. If those were the only candidates, the digits could be swapped in any solution, so a
+// puzzle with a unique solution can't end up in that state.
+struct Rectangle {
+    // The corners, ordered top left, top right, bottom left, bottom right
+    cells: [Cell; 4],
+    digits: u16,
+}
+
+impl Rectangle {
+    // Cells that only have the two digits left
+    fn floor(&self, board: &Board) -> Vec<Cell> {
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&cell| board.candidates(cell) == self.digits)
+            .collect()
+    }
+
+    fn roof(&self, board: &Board) -> Vec<Cell> {
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&cell| board.candidates(cell) != self.digits)
+            .collect()
+    }
+
+    fn opposite(&self, cell: Cell) -> Cell {
+        let pos = self.cells.iter().position(|&x| x == cell).unwrap_or(0);
+        self.cells[3 - pos]
+    }
+
+    fn step(
+        &self,
+        technique: Technique,
+        placement: Option<(Cell, u8)>,
+        eliminations: Vec<(Cell, u8)>,
+    ) -> Option<Step> {
+        if eliminations.is_empty() && placement.is_none() {
+            return None;
+        }
+        Some(Step {
+            technique,
+            pattern: Pattern::Rectangle {
+                cells: self.cells.to_vec(),
+                digits: self.digits,
+            },
+            placement,
+            eliminations,
+        })
+    }
+}
+
+fn rectangles(board: &Board) -> Vec<Rectangle> {
+    let mut ret = vec![];
+    for rows in combinations(&(0..9).collect::<Vec<u8>>(), 2) {
+        for cols in combinations(&(0..9).collect::<Vec<u8>>(), 2) {
+            let cells = [
+                Cell(rows[0] * 9 + cols[0]),
+                Cell(rows[0] * 9 + cols[1]),
+                Cell(rows[1] * 9 + cols[0]),
+                Cell(rows[1] * 9 + cols[1]),
+            ];
+            let mut blocks: Vec<_> = cells.iter().map(|cell| cell.block()).collect();
+            blocks.sort();
+            blocks.dedup();
+            if blocks.len() != 2 {
+                continue;
+            }
+            let common = cells
+                .iter()
+                .fold(0x1FF, |mask, &cell| mask & board.candidates(cell));
+            for pair in combinations(&mask_to_numbers(common).collect::<Vec<u8>>(), 2) {
+                ret.push(Rectangle {
+                    cells,
+                    digits: number_to_mask(pair[0]) | number_to_mask(pair[1]),
+                });
+            }
+        }
+    }
+    ret
+}
+
+fn shared_houses(a: Cell, b: Cell) -> Vec<Region> {
+    vec![
+        Region::Row(a.row()),
+        Region::Col(a.col()),
+        Region::Block(a.block()),
+    ]
+    .into_iter()
+    .filter(|house| house.contains(b))
+    .collect()
+}
+
+fn cells_seeing_all(board: &Board, digit: u8, cells: &[Cell]) -> Vec<(Cell, u8)> {
+    (0..81)
+        .map(Cell)
+        .filter(|&cell| board.candidates(cell) & number_to_mask(digit) > 0)
+        .filter(|&cell| cells.iter().all(|&other| other.sees(cell)))
+        .map(|cell| (cell, digit))
+        .collect()
+}
+
+// Three corners only have the two digits, so the fourth has to be something else
+pub fn type_1(board: &Board) -> Option<Step> {
+    rectangles(board).into_iter().find_map(|rect| {
+        let roof = rect.roof(board);
+        if roof.len() != 1 {
+            return None;
+        }
+        let eliminations = mask_to_numbers(rect.digits)
+            .map(|digit| (roof[0], digit))
+            .collect();
+        rect.step(Technique::UniqueRectangle1, None, eliminations)
+    })
+}
+
+// The corners that have more than the two digits all have the same single extra candidate, one of
+// them has to be that candidate. Type 2 if those corners share a line, Type 5 otherwise.
+fn extra_digit(board: &Board, technique: Technique) -> Option<Step> {
+    rectangles(board).into_iter().find_map(|rect| {
+        let roof = rect.roof(board);
+        if roof.len() < 2 {
+            return None;
+        }
+        let extra = board.candidates(roof[0]) & !rect.digits;
+        if extra.count_ones() != 1
+            || roof
+                .iter()
+                .any(|&cell| board.candidates(cell) != rect.digits | extra)
+        {
+            return None;
+        }
+        let adjacent = roof.len() == 2 && roof[0].sees(roof[1]);
+        if adjacent != (technique == Technique::UniqueRectangle2) {
+            return None;
+        }
+        let digit = mask_to_numbers(extra).next()?;
+        rect.step(technique, None, cells_seeing_all(board, digit, &roof))
+    })
+}
+
+pub fn type_2(board: &Board) -> Option<Step> {
+    extra_digit(board, Technique::UniqueRectangle2)
+}
+
+// The extra candidates of two corners in one line form a naked subset with other cells of a house
+// they share
+pub fn type_3(board: &Board) -> Option<Step> {
+    for rect in rectangles(board) {
+        let roof = rect.roof(board);
+        if roof.len() != 2 || !roof[0].sees(roof[1]) {
+            continue;
+        }
+        let extra = roof
+            .iter()
+            .fold(0u16, |mask, &cell| mask | board.candidates(cell))
+            & !rect.digits;
+        for house in shared_houses(roof[0], roof[1]) {
+            let others: Vec<Cell> = house
+                .into_iter()
+                .filter(|cell| board.candidates(*cell) != 0 && !roof.contains(cell))
+                .collect();
+            for size in 1..=3 {
+                for cells in combinations(&others, size) {
+                    let digits = cells
+                        .iter()
+                        .fold(extra, |mask, &cell| mask | board.candidates(cell));
+                    if digits.count_ones() as usize != size + 1 {
+                        continue;
+                    }
+                    let eliminations: Vec<(Cell, u8)> = others
+                        .iter()
+                        .filter(|cell| !cells.contains(cell))
+                        .flat_map(|&cell| {
+                            mask_to_numbers(board.candidates(cell) & digits)
+                                .map(move |digit| (cell, digit))
+                        })
+                        .collect();
+                    let step = rect.step(Technique::UniqueRectangle3, None, eliminations);
+                    if step.is_some() {
+                        return step;
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+// One of the digits has no other place in a house shared by the two corners with extra
+// candidates. Both of them can't be that digit, so the other digit can't go in either.
+pub fn type_4(board: &Board) -> Option<Step> {
+    for rect in rectangles(board) {
+        let roof = rect.roof(board);
+        if roof.len() != 2 || !roof[0].sees(roof[1]) {
+            continue;
+        }
+        for house in shared_houses(roof[0], roof[1]) {
+            for digit in mask_to_numbers(rect.digits) {
+                let places = places_for(board, house, digit);
+                if places.len() != 2 || places.iter().any(|cell| !roof.contains(cell)) {
+                    continue;
+                }
+                let other = mask_to_numbers(rect.digits & !number_to_mask(digit)).next()?;
+                let eliminations = roof.iter().map(|&cell| (cell, other)).collect();
+                let step = rect.step(Technique::UniqueRectangle4, None, eliminations);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+pub fn type_5(board: &Board) -> Option<Step> {
+    extra_digit(board, Technique::UniqueRectangle5)
+}
+
+// Two opposite corners only have the two digits, and one of the digits is only found in the
+// rectangle in both rows (or both columns). Putting it in one of the other corners would force
+// it into the other one as well, leaving the deadly pattern.
+pub fn type_6(board: &Board) -> Option<Step> {
+    for rect in rectangles(board) {
+        let floor = rect.floor(board);
+        if floor.len() != 2 || floor[0].sees(floor[1]) {
+            continue;
+        }
+        let roof = rect.roof(board);
+        for digit in mask_to_numbers(rect.digits) {
+            let confined = |lines: [Region; 2]| {
+                lines.iter().all(|&line| {
+                    places_for(board, line, digit)
+                        .iter()
+                        .all(|cell| rect.cells.contains(cell))
+                })
+            };
+            let rows = [
+                Region::Row(rect.cells[0].row()),
+                Region::Row(rect.cells[3].row()),
+            ];
+            let cols = [
+                Region::Col(rect.cells[0].col()),
+                Region::Col(rect.cells[3].col()),
+            ];
+            if confined(rows) || confined(cols) {
+                let eliminations = roof.iter().map(|&cell| (cell, digit)).collect();
+                let step = rect.step(Technique::UniqueRectangle6, None, eliminations);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+// A corner with only the two digits, where one of the digits has no other place in the row and
+// column of the opposite corner than the rectangle. The opposite corner can't be the other digit,
+// as that would force the deadly pattern.
+pub fn hidden_rectangle(board: &Board) -> Option<Step> {
+    for rect in rectangles(board) {
+        for corner in rect.floor(board) {
+            let opposite = rect.opposite(corner);
+            for digit in mask_to_numbers(rect.digits) {
+                let lines = [Region::Row(opposite.row()), Region::Col(opposite.col())];
+                let confined = lines.iter().all(|&line| {
+                    places_for(board, line, digit)
+                        .iter()
+                        .all(|cell| rect.cells.contains(cell))
+                });
+                if !confined {
+                    continue;
+                }
+                let other = mask_to_numbers(rect.digits & !number_to_mask(digit)).next()?;
+                let step = rect.step(Technique::HiddenRectangle, None, vec![(opposite, other)]);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+// Bivalue Universal Grave: if every unsolved cell but one had exactly two candidates, every digit
+// would appear twice in each house and the puzzle would have two solutions. So the one cell with
+// three candidates has to be the digit that would otherwise appear three times in its houses.
+pub fn bug_plus_one(board: &Board) -> Option<Step> {
+    let mut extra_cell = None;
+    for cell in (0..81).map(Cell) {
+        match board.candidates(cell).count_ones() {
+            0 | 2 => {}
+            3 if extra_cell.is_none() => extra_cell = Some(cell),
+            _ => return None,
+        }
+    }
+    let cell = extra_cell?;
+    let digit = mask_to_numbers(board.candidates(cell)).find(|&digit| {
+        [
+            Region::Row(cell.row()),
+            Region::Col(cell.col()),
+            Region::Block(cell.block()),
+        ]
+        .iter()
+        .all(|&house| places_for(board, house, digit).len() == 3)
+    })?;
+    Some(Step {
+        technique: Technique::BugPlusOne,
+        pattern: Pattern::Bug { cell, digit },
+        placement: Some((cell, digit)),
+        eliminations: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::board::Board;
+    use crate::solver::calc::{number_to_mask, Cell};
+    use crate::solver::logic::tests::{assert_step, grid};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn unique_rectangle_1() {
+        let board = grid(
+            "
+            9  8   2457 | 3   6   245  | 247  1     27
+            6  57  2457 | 145 124 8    | 2479 279   3
+            1  3   24   | 9   7   24   | 5    6     8
+            ------------+--------------+----------------
+            25 479 79   | 8   49  1    | 3    25    6
+            25 149 6    | 7   3   49   | 1289 2589  1259
+            3  19  8    | 2   5   6    | 179  4     179
+            ------------+--------------+----------------
+            7  6   59   | 45  8   2459 | 129  3     1259
+            8  2   3    | 15  19  7    | 6    59    4
+            4  59  1    | 6   29  3    | 2789 25789 2579
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::UniqueRectangle1,
+            "Unique Rectangle Type 1: {25} in r4c1,r4c8,r5c1,r5c8 must not form a deadly pattern \
+             => r5c8<>2, r5c8<>5",
+        );
+    }
+
+    #[test]
+    fn unique_rectangle_2() {
+        let board = grid(
+            "
+            7   2   69  | 1 3  69  | 5    8   4
+            38  4   1   | 5 2  89  | 3679 369 379
+            5   368 689 | 7 4  689 | 1    39  2
+            ------------+----------+-------------
+            689 7   68  | 3 89 5   | 2    4   1
+            189 18  3   | 4 7  2   | 89   5   6
+            2   5   4   | 6 89 1   | 389  7   39
+            ------------+----------+-------------
+            368 368 7   | 9 1  4   | 36   2   5
+            4   13  5   | 2 6  37  | 379  139 8
+            16  9   2   | 8 5  37  | 4    16  37
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::UniqueRectangle2,
+            "Unique Rectangle Type 2: {69} in r1c3,r1c6,r3c3,r3c6 must not form a deadly pattern \
+             => r3c2<>8",
+        );
+    }
+
+    #[test]
+    fn unique_rectangle_3() {
+        let board = grid(
+            "
+            128   1238 6    | 7    5  238  | 1389   4     139
+            1478  1348 5    | 348  9  3468 | 1368   1378  2
+            2478  9    234  | 1    38 2468 | 68     5     67
+            ----------------+--------------+-----------------
+            259   23   1    | 35   4  7    | 29     6     8
+            56    7    8    | 56   2  9    | 13     13    4
+            2469  24   2349 | 68   38 1    | 7      29    5
+            ----------------+--------------+-----------------
+            12489 5    249  | 3489 7  348  | 124689 1289  169
+            489   6    7    | 2    1  5    | 3489   389   39
+            3     1248 249  | 489  6  48   | 5      12789 179
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::UniqueRectangle3,
+            "Unique Rectangle Type 3: {13} in r2c7,r2c8,r5c7,r5c8 must not form a deadly pattern \
+             => r1c7<>8",
+        );
+    }
+
+    #[test]
+    fn unique_rectangle_4() {
+        let board = grid(
+            "
+            389 1   237 | 6 358 29 | 4 35   357
+            38  67  5   | 4 38  1  | 9 2    67
+            4   69  23  | 7 35  29 | 1 8    356
+            ------------+----------+-----------
+            16  3   8   | 5 9   7  | 2 146  14
+            2   457 47  | 3 1   46 | 8 56   9
+            16  45  9   | 2 46  8  | 7 1356 35
+            ------------+----------+-----------
+            5   8   16  | 9 7   46 | 3 14   2
+            39  49  34  | 1 2   5  | 6 7    8
+            7   2   16  | 8 46  3  | 5 9    14
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::UniqueRectangle4,
+            "Unique Rectangle Type 4: {38} in r1c1,r1c5,r2c1,r2c5 must not form a deadly pattern \
+             => r1c1<>3, r1c5<>3",
+        );
+    }
+
+    // The corners with the extra digit are hardly ever diagonal in real puzzles, so the board is
+    // built by hand
+    #[test]
+    fn unique_rectangle_5() {
+        let mut board = Board::new();
+        for &(cell, digits) in &[(0, 0b111), (3, 0b11), (9, 0b11), (12, 0b111)] {
+            for digit in (0..9).filter(|&digit| digits & number_to_mask(digit) == 0) {
+                board.eliminate(Cell(cell), digit).unwrap();
+            }
+        }
+        assert_step(
+            &board,
+            Technique::UniqueRectangle5,
+            "Unique Rectangle Type 5: {12} in r1c1,r1c4,r2c1,r2c4 must not form a deadly pattern \
+             => r1c5<>3, r1c6<>3, r2c2<>3, r2c3<>3",
+        );
+    }
+
+    #[test]
+    fn unique_rectangle_6() {
+        let board = grid(
+            "
+            238 5    7   | 89   4  38   | 289 1   6
+            28  4    69  | 5    1  678  | 279 3   278
+            38  1    69  | 6789 2  3678 | 5   78  4
+            -------------+--------------+-------------
+            6   278  5   | 4    37 9    | 1   278 2378
+            17  3    128 | 278  6  278  | 4   5   9
+            4   9    28  | 2378 5  1    | 278 6   2378
+            -------------+--------------+-------------
+            17  278  4   | 237  37 5    | 6   9   1278
+            9   267  3   | 1    8  267  | 27  4   5
+            5   2678 128 | 267  9  4    | 3   278 1278
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::UniqueRectangle6,
+            "Unique Rectangle Type 6: {38} in r1c1,r1c6,r3c1,r3c6 must not form a deadly pattern \
+             => r1c1<>3, r3c6<>3",
+        );
+    }
+
+    #[test]
+    fn hidden_rectangle() {
+        let board = grid(
+            "
+            8     129  7    | 6 129 139 | 4     5    139
+            4     23   19   | 5 23  78  | 179   1789 6
+            13569 1569 1569 | 4 19  78  | 179   2    13789
+            ----------------+-----------+-----------------
+            1269  7    169  | 3 4   5   | 8     19   129
+            159   4    8    | 2 169 169 | 1579  3    1579
+            1259  159  3    | 7 8   19  | 1259  6    4
+            ----------------+-----------+-----------------
+            156   8    1456 | 9 7   26  | 3     14   125
+            1369  169  1469 | 8 5   236 | 12679 1479 1279
+            7     3569 2    | 1 36  4   | 569   89   589
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::HiddenRectangle,
+            "Hidden Unique Rectangle: {14} in r7c3,r7c8,r8c3,r8c8 must not form a deadly pattern \
+             => r8c3<>1",
+        );
+    }
+
+    #[test]
+    fn bug_plus_one() {
+        let board = grid(
+            "
+            28 5  7  | 89 4  3  | 29  1  6
+            28 4  69 | 5  1  68 | 279 3  27
+            3  1  69 | 79 2  67 | 5   8  4
+            ---------+----------+----------
+            6  8  5  | 4  37 9  | 1   27 23
+            7  3  1  | 28 6  28 | 4   5  9
+            4  9  2  | 37 5  1  | 8   6  37
+            ---------+----------+----------
+            1  27 4  | 23 37 5  | 6   9  8
+            9  6  3  | 1  8  27 | 27  4  5
+            5  27 8  | 6  9  4  | 3   27 1
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::BugPlusOne,
+            "BUG+1: r2c7 is the only cell with three candidates, and 2 appears three times in its \
+             houses => r2c7=2",
+        );
+    }
+}
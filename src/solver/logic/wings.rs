@@ -0,0 +1,237 @@
+use crate::solver::board::Board;
+use crate::solver::calc::{mask_to_numbers, number_to_mask, Cell, Region};
+use crate::solver::logic::{places_for, Pattern, Step, Technique};
+
+fn cells_with_count(board: &Board, count: u32) -> Vec<Cell> {
+    (0..81)
+        .map(Cell)
+        .filter(|&cell| board.candidates(cell).count_ones() == count)
+        .collect()
+}
+
+// Removes the digit from every cell that sees all of the given cells
+fn eliminations_seeing(board: &Board, digit: u8, cells: &[Cell]) -> Vec<(Cell, u8)> {
+    (0..81)
+        .map(Cell)
+        .filter(|&cell| board.candidates(cell) & number_to_mask(digit) > 0)
+        .filter(|&cell| cells.iter().all(|&other| other.sees(cell)))
+        .map(|cell| (cell, digit))
+        .collect()
+}
+
+fn wing_step(
+    technique: Technique,
+    pivot: Vec<Cell>,
+    pincers: Vec<Cell>,
+    digit: u8,
+    eliminations: Vec<(Cell, u8)>,
+) -> Option<Step> {
+    if eliminations.is_empty() {
+        return None;
+    }
+    Some(Step {
+        technique,
+        pattern: Pattern::Wing {
+            pivot,
+            pincers,
+            digit,
+        },
+        placement: None,
+        eliminations,
+    })
+}
+
+// A pivot {xy}, that sees two pincers {xz} and {yz}. Whatever the pivot holds, one of the pincers
+// has to be z.
+pub fn xy_wing(board: &Board) -> Option<Step> {
+    let bivalue = cells_with_count(board, 2);
+    for &pivot in &bivalue {
+        let pivot_mask = board.candidates(pivot);
+        let pincers: Vec<Cell> = bivalue
+            .iter()
+            .copied()
+            .filter(|&cell| pivot.sees(cell))
+            .filter(|&cell| (board.candidates(cell) & pivot_mask).count_ones() == 1)
+            .collect();
+        for (i, &a) in pincers.iter().enumerate() {
+            for &b in &pincers[i + 1..] {
+                let a_mask = board.candidates(a);
+                let b_mask = board.candidates(b);
+                let z = a_mask & b_mask & !pivot_mask;
+                if z == 0 || (a_mask | b_mask) & pivot_mask != pivot_mask {
+                    continue;
+                }
+                let digit = mask_to_numbers(z).next()?;
+                let step = wing_step(
+                    Technique::XYWing,
+                    vec![pivot],
+                    vec![a, b],
+                    digit,
+                    eliminations_seeing(board, digit, &[a, b]),
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+// A pivot {xyz}, that sees two pincers {xz} and {yz}. One of the three cells has to be z.
+pub fn xyz_wing(board: &Board) -> Option<Step> {
+    let bivalue = cells_with_count(board, 2);
+    for pivot in cells_with_count(board, 3) {
+        let pivot_mask = board.candidates(pivot);
+        let pincers: Vec<Cell> = bivalue
+            .iter()
+            .copied()
+            .filter(|&cell| pivot.sees(cell))
+            .filter(|&cell| board.candidates(cell) & !pivot_mask == 0)
+            .collect();
+        for (i, &a) in pincers.iter().enumerate() {
+            for &b in &pincers[i + 1..] {
+                let a_mask = board.candidates(a);
+                let b_mask = board.candidates(b);
+                let z = a_mask & b_mask;
+                if a_mask == b_mask || z.count_ones() != 1 {
+                    continue;
+                }
+                let digit = mask_to_numbers(z).next()?;
+                let step = wing_step(
+                    Technique::XYZWing,
+                    vec![pivot],
+                    vec![a, b],
+                    digit,
+                    eliminations_seeing(board, digit, &[pivot, a, b]),
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+// Two pincers {xy} that don't see each other, connected by a strong link on x: two cells that
+// are the only places for x in a house. One of the pincers can't be x, so it has to be y.
+pub fn w_wing(board: &Board) -> Option<Step> {
+    let bivalue = cells_with_count(board, 2);
+    for (i, &a) in bivalue.iter().enumerate() {
+        for &b in &bivalue[i + 1..] {
+            let mask = board.candidates(a);
+            if board.candidates(b) != mask || a.sees(b) {
+                continue;
+            }
+            for x in mask_to_numbers(mask) {
+                let y = mask_to_numbers(mask & !number_to_mask(x)).next()?;
+                for region in Region::all() {
+                    let link = match places_for(board, region, x)[..] {
+                        [first, second] => [first, second],
+                        _ => continue,
+                    };
+                    let connects = |(start, end): (Cell, Cell)| {
+                        start != a && end != b && start.sees(a) && end.sees(b)
+                    };
+                    let link = if connects((link[0], link[1])) {
+                        vec![link[0], link[1]]
+                    } else if connects((link[1], link[0])) {
+                        vec![link[1], link[0]]
+                    } else {
+                        continue;
+                    };
+                    let step = wing_step(
+                        Technique::WWing,
+                        link,
+                        vec![a, b],
+                        y,
+                        eliminations_seeing(board, y, &[a, b]),
+                    );
+                    if step.is_some() {
+                        return step;
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::logic::tests::{assert_step, grid};
+    use crate::solver::logic::Technique;
+
+    #[test]
+    fn xy_wing() {
+        let board = grid(
+            "
+            68  468 279 | 1  789 5  | 247  3  279
+            5   13  79  | 39 2   4  | 17   8  6
+            13  48  279 | 6  789 37 | 1247 5  1279
+            ------------+-----------+-------------
+            4   9   1   | 2  3   6  | 5    7  8
+            2   5   6   | 8  17  17 | 9    4  3
+            37  37  8   | 5  4   9  | 126  16 12
+            ------------+-----------+-------------
+            169 2   5   | 7  19  8  | 3    16 4
+            179 17  4   | 39 6   13 | 8    2  5
+            168 68  3   | 4  5   2  | 167  9  17
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::XYWing,
+            "XY-Wing: pivot r2c4 with pincers r2c3,r3c6 on 7 => r3c3<>7",
+        );
+    }
+
+    #[test]
+    fn xyz_wing() {
+        let board = grid(
+            "
+            8   2  34  | 6  1   34  | 7   5  9
+            5   1  7   | 89 89  2   | 3   4  6
+            34  6  9   | 57 37  345 | 1   2  8
+            -----------+------------+---------
+            134 59 346 | 24 369 139 | 259 8  7
+            14  7  468 | 24 5   189 | 29  16 3
+            2   59 368 | 18 368 7   | 59  16 4
+            -----------+------------+---------
+            7   8  2   | 19 4   19  | 6   3  5
+            6   3  1   | 57 78  58  | 4   9  2
+            9   4  5   | 3  2   6   | 8   7  1
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::XYZWing,
+            "XYZ-Wing: pivot r5c6 with pincers r6c4,r7c6 on 1 => r4c6<>1",
+        );
+    }
+
+    #[test]
+    fn w_wing() {
+        let board = grid(
+            "
+            68  468 279 | 1  789 5  | 247  3  279
+            5   13  79  | 39 2   4  | 17   8  6
+            13  48  29  | 6  789 37 | 1247 5  1279
+            ------------+-----------+-------------
+            4   9   1   | 2  3   6  | 5    7  8
+            2   5   6   | 8  17  17 | 9    4  3
+            37  37  8   | 5  4   9  | 126  16 12
+            ------------+-----------+-------------
+            169 2   5   | 7  19  8  | 3    16 4
+            179 17  4   | 39 6   13 | 8    2  5
+            168 68  3   | 4  5   2  | 167  9  17
+            ",
+        );
+        assert_step(
+            &board,
+            Technique::WWing,
+            "W-Wing: pivot r2c4,r3c6 with pincers r2c2,r8c6 on 1 => r8c2<>1",
+        );
+    }
+}
@@ -1,4 +1,11 @@
+pub mod analysis;
+pub mod bitboard;
 pub mod board;
 pub mod calc;
+pub mod dlx;
+pub mod generate;
+pub mod logic;
 pub mod precalc;
+pub mod rating;
 pub mod solve;
+pub mod transform;
@@ -0,0 +1,103 @@
+use crate::solver::calc::Cell;
+
+// Lookup tables for the geometry of the grid, computed at compile time so that walking houses and
+// peers never has to allocate
+
+pub const CELL_ROW: [u8; 81] = cell_table(0);
+pub const CELL_COL: [u8; 81] = cell_table(1);
+pub const CELL_BLOCK: [u8; 81] = cell_table(2);
+
+// The cells of each house, in the same order the houses are indexed in
+pub const ROW_CELLS: [[Cell; 9]; 9] = house_table(0);
+pub const COL_CELLS: [[Cell; 9]; 9] = house_table(1);
+pub const BLOCK_CELLS: [[Cell; 9]; 9] = house_table(2);
+
+// The 20 other cells that share a row, column or block with each cell, in ascending order
+pub const PEERS: [[Cell; 20]; 81] = peer_table();
+
+const fn house_of(kind: usize, cell: usize) -> u8 {
+    let (row, col) = (cell / 9, cell % 9);
+    (match kind {
+        0 => row,
+        1 => col,
+        _ => row / 3 * 3 + col / 3,
+    }) as u8
+}
+
+const fn cell_table(kind: usize) -> [u8; 81] {
+    let mut table = [0; 81];
+    let mut cell = 0;
+    while cell < 81 {
+        table[cell] = house_of(kind, cell);
+        cell += 1;
+    }
+    table
+}
+
+const fn house_table(kind: usize) -> [[Cell; 9]; 9] {
+    let mut table = [[Cell(0); 9]; 9];
+    let mut filled = [0; 9];
+    let mut cell = 0;
+    while cell < 81 {
+        let house = house_of(kind, cell) as usize;
+        table[house][filled[house]] = Cell(cell as u8);
+        filled[house] += 1;
+        cell += 1;
+    }
+    table
+}
+
+const fn peer_table() -> [[Cell; 20]; 81] {
+    let mut table = [[Cell(0); 20]; 81];
+    let mut cell = 0;
+    while cell < 81 {
+        let mut filled = 0;
+        let mut other = 0;
+        while other < 81 {
+            if other != cell
+                && (house_of(0, cell) == house_of(0, other)
+                    || house_of(1, cell) == house_of(1, other)
+                    || house_of(2, cell) == house_of(2, other))
+            {
+                table[cell][filled] = Cell(other as u8);
+                filled += 1;
+            }
+            other += 1;
+        }
+        cell += 1;
+    }
+    table
+}
+
+// The same tables as bit sets over the cells, for the bitboard solver. Houses are indexed rows
+// first, then columns, then blocks.
+pub const PEER_MASKS: [u128; 81] = peer_mask_table();
+pub const HOUSE_MASKS: [u128; 27] = house_mask_table();
+
+const fn peer_mask_table() -> [u128; 81] {
+    let mut table = [0; 81];
+    let mut cell = 0;
+    while cell < 81 {
+        let mut i = 0;
+        while i < 20 {
+            table[cell] |= 1 << PEERS[cell][i].0;
+            i += 1;
+        }
+        cell += 1;
+    }
+    table
+}
+
+const fn house_mask_table() -> [u128; 27] {
+    let mut table = [0; 27];
+    let mut cell = 0;
+    while cell < 81 {
+        let mut kind = 0;
+        while kind < 3 {
+            table[kind * 9 + house_of(kind, cell) as usize] |= 1 << cell;
+            kind += 1;
+        }
+        cell += 1;
+    }
+    table
+}
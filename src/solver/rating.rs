@@ -0,0 +1,45 @@
+use crate::errors::PuzzleError;
+use crate::solver::board::Board;
+use crate::solver::logic::{solve_logically, LogicOptions, Technique};
+
+// How hard a puzzle is to solve by logic alone
+pub struct Rating {
+    // The rating of the hardest step, on the scale of Sudoku Explainer
+    pub rating: f32,
+    pub hardest: Option<Technique>,
+    // How often each technique was used, from the easiest to the hardest
+    pub counts: Vec<(Technique, usize)>,
+    // The logic got stuck before the puzzle was solved. The rating only covers the steps up to
+    // that point.
+    pub needs_guessing: bool,
+}
+
+pub fn rate(board: Board, options: &LogicOptions) -> Result<Rating, PuzzleError> {
+    let explanation = solve_logically(board, options)?;
+    let mut rating = 1.0;
+    let mut hardest = None;
+    for step in &explanation.steps {
+        if step.rating() > rating {
+            rating = step.rating();
+            hardest = Some(step.technique);
+        }
+    }
+    let counts = Technique::ALL
+        .iter()
+        .map(|&technique| {
+            let count = explanation
+                .steps
+                .iter()
+                .filter(|step| step.technique == technique)
+                .count();
+            (technique, count)
+        })
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    Ok(Rating {
+        rating,
+        hardest,
+        counts,
+        needs_guessing: !explanation.board.is_solved(),
+    })
+}
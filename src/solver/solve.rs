@@ -1,38 +1,141 @@
-use crate::errors::NonUniqueError;
+use crate::errors::PuzzleError;
+use crate::solver::bitboard::Bitboard;
 use crate::solver::board::Board;
 use crate::solver::calc::number_to_mask;
+use crate::solver::dlx::Dlx;
+use std::str::FromStr;
 
-pub fn solve(board: Board, prove_unique: bool) -> Result<Option<Board>, NonUniqueError> {
-    let next = board.most_certain();
-    match next {
-        None => return Ok(Some(board)),
-        Some((next_cell, possibilities)) => {
-            let mut already_found = None;
-            for i in (0..9).filter(|&i| possibilities.mask & number_to_mask(i) > 0) {
-                let mut new_board = board.clone();
-                let marking = new_board.mark(next_cell, i);
-                if marking.is_err() {
-                    continue;
-                }
+// The algorithms available to search for solutions. They all guess the same way, so they find the
+// same solutions in the same order.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Engine {
+    Backtrack,
+    Dlx,
+    Bitboard,
+}
+
+impl Engine {
+    pub const ALL: [Engine; 3] = [Engine::Backtrack, Engine::Dlx, Engine::Bitboard];
+    pub const NAMES: [&'static str; 3] = ["backtrack", "dlx", "bitboard"];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Engine::Backtrack => "backtrack",
+            Engine::Dlx => "dlx",
+            Engine::Bitboard => "bitboard",
+        }
+    }
+
+    pub fn solutions(self, board: Board) -> Box<dyn Iterator<Item = Board>> {
+        match self {
+            Engine::Backtrack => Box::new(solutions(board)),
+            Engine::Dlx => Box::new(Dlx::new(&board)),
+            Engine::Bitboard => Box::new(Bitboard::new(&board)),
+        }
+    }
+
+    pub fn solve(self, board: Board, prove_unique: bool) -> Result<Option<Board>, PuzzleError> {
+        let mut solutions = self.solutions(board);
+        let first = solutions.next();
+        if prove_unique && first.is_some() && solutions.next().is_some() {
+            return Err(PuzzleError::Ambiguous);
+        }
+        Ok(first)
+    }
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        Engine::ALL
+            .iter()
+            .copied()
+            .find(|engine| engine.name() == name)
+            .ok_or_else(|| format!("Unknown engine '{}'", s))
+    }
+}
 
-                match solve(new_board, prove_unique)? {
-                    Some(solved_board) => {
-                        if !prove_unique {
-                            return Ok(Some(solved_board));
-                        } else if already_found == None {
-                            already_found = Some(solved_board);
-                        } else {
-                            return Err(NonUniqueError {});
-                        }
-                    }
-                    None => continue,
+pub fn solve(board: Board, prove_unique: bool) -> Result<Option<Board>, PuzzleError> {
+    Engine::Backtrack.solve(board, prove_unique)
+}
+
+// Lazily yields every solution of the board. Always guesses the cell with the fewest possible
+// values next, trying the smaller values first.
+pub struct Solutions {
+    // Boards that still have to be explored, the next one on top
+    stack: Vec<Board>,
+}
+
+pub fn solutions(board: Board) -> Solutions {
+    Solutions { stack: vec![board] }
+}
+
+impl Iterator for Solutions {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        while let Some(board) = self.stack.pop() {
+            let (next_cell, possibilities) = match board.most_certain() {
+                None => return Some(board),
+                Some(next) => next,
+            };
+            // Pushed in reverse, so the smallest value ends up on top
+            for i in (0..9)
+                .rev()
+                .filter(|&i| possibilities.mask & number_to_mask(i) > 0)
+            {
+                let mut new_board = board.clone();
+                if new_board.mark(next_cell, i).is_ok() {
+                    self.stack.push(new_board);
                 }
             }
-            if let Some(board) = already_found {
-                return Ok(Some(board));
-            }
         }
+        None
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLUTIONS: usize = 50;
 
-    Ok(None)
+    fn assert_same_order(puzzle: &str) {
+        let nums = puzzle.bytes().map(|x| x - b'0').collect();
+        let board = Board::from_puzzle(nums).unwrap();
+        let first: Vec<Vec<String>> = Engine::ALL
+            .iter()
+            .map(|engine| {
+                engine
+                    .solutions(board.clone())
+                    .take(SOLUTIONS)
+                    .map(|solution| solution.to_1d_string())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(first[0].len(), SOLUTIONS);
+        for (engine, solutions) in Engine::ALL.iter().zip(&first).skip(1) {
+            assert_eq!(
+                solutions,
+                &first[0],
+                "{} finds the solutions of {} in another order",
+                engine.name(),
+                puzzle
+            );
+        }
+    }
+
+    #[test]
+    fn same_order_empty_grid() {
+        assert_same_order(&"0".repeat(81));
+    }
+
+    #[test]
+    fn same_order_ambiguous() {
+        assert_same_order(
+            "100000002090400050006000700050903000000070000000850040700000600030009080002000000",
+        );
+    }
 }
@@ -0,0 +1,404 @@
+use crate::solver::board::{Board, CellState, Possibilities};
+use crate::solver::calc::number_to_mask;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+// The orders three rows of a band (or bands of a grid) can be put in
+const PERMUTATIONS_3: [[u8; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+// An element of the sudoku symmetry group: everything that can be done to a grid without
+// changing its logic. The grid is transposed first, then row r of the result is taken from row
+// rows[r], column c from column cols[c], and every digit d is replaced by digits[d].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Transform {
+    pub transpose: bool,
+    pub rows: [u8; 9],
+    pub cols: [u8; 9],
+    pub digits: [u8; 9],
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        transpose: false,
+        rows: [0, 1, 2, 3, 4, 5, 6, 7, 8],
+        cols: [0, 1, 2, 3, 4, 5, 6, 7, 8],
+        digits: [0, 1, 2, 3, 4, 5, 6, 7, 8],
+    };
+
+    pub fn transposed() -> Self {
+        Transform {
+            transpose: true,
+            ..Transform::IDENTITY
+        }
+    }
+
+    // Turns the grid clockwise by the given number of quarter turns
+    pub fn rotated(quarters: u8) -> Self {
+        match quarters % 4 {
+            0 => Transform::IDENTITY,
+            1 => Transform::transposed().then(&Transform::mirrored_vertically()),
+            2 => Transform::mirrored_horizontally().then(&Transform::mirrored_vertically()),
+            _ => Transform::transposed().then(&Transform::mirrored_horizontally()),
+        }
+    }
+
+    // Mirrors at the horizontal center line, so the top row becomes the bottom row
+    pub fn mirrored_horizontally() -> Self {
+        Transform {
+            rows: [8, 7, 6, 5, 4, 3, 2, 1, 0],
+            ..Transform::IDENTITY
+        }
+    }
+
+    // Mirrors at the vertical center line, so the left column becomes the right column
+    pub fn mirrored_vertically() -> Self {
+        Transform {
+            cols: [8, 7, 6, 5, 4, 3, 2, 1, 0],
+            ..Transform::IDENTITY
+        }
+    }
+
+    // Replaces every digit d with digits[d]
+    pub fn relabeled(digits: [u8; 9]) -> Self {
+        Transform {
+            digits,
+            ..Transform::IDENTITY
+        }
+    }
+
+    // Puts the rows in a new order, where row r is taken from row rows[r]. Rows can only move
+    // within their band, and bands only as a whole.
+    pub fn reordered_rows(rows: [u8; 9]) -> Result<Self, String> {
+        check_line_order(&rows)?;
+        Ok(Transform {
+            rows,
+            ..Transform::IDENTITY
+        })
+    }
+
+    pub fn reordered_cols(cols: [u8; 9]) -> Result<Self, String> {
+        check_line_order(&cols)?;
+        Ok(Transform {
+            cols,
+            ..Transform::IDENTITY
+        })
+    }
+
+    // Puts the bands in a new order, where band b is taken from band bands[b]
+    pub fn reordered_bands(bands: [u8; 3]) -> Result<Self, String> {
+        Transform::reordered_rows(expand_blocks(bands))
+    }
+
+    pub fn reordered_stacks(stacks: [u8; 3]) -> Result<Self, String> {
+        Transform::reordered_cols(expand_blocks(stacks))
+    }
+
+    // A random element of the symmetry group
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        let mut digits = Transform::IDENTITY.digits;
+        digits.shuffle(rng);
+        Transform {
+            transpose: rng.gen(),
+            rows: random_line_order(rng),
+            cols: random_line_order(rng),
+            digits,
+        }
+    }
+
+    // The transformation that does this one first, and then the other one
+    pub fn then(&self, other: &Transform) -> Self {
+        // Transposing after reordering is the same as reordering the other way after transposing
+        let (rows, cols) = if other.transpose {
+            (self.cols, self.rows)
+        } else {
+            (self.rows, self.cols)
+        };
+        let mut ret = Transform {
+            transpose: self.transpose != other.transpose,
+            ..Transform::IDENTITY
+        };
+        for i in 0..9 {
+            ret.rows[i] = rows[other.rows[i] as usize];
+            ret.cols[i] = cols[other.cols[i] as usize];
+            ret.digits[i] = other.digits[self.digits[i] as usize];
+        }
+        ret
+    }
+
+    // The transformation that undoes this one
+    pub fn inverse(&self) -> Self {
+        let (rows, cols) = if self.transpose {
+            (self.cols, self.rows)
+        } else {
+            (self.rows, self.cols)
+        };
+        let mut ret = Transform {
+            transpose: self.transpose,
+            ..Transform::IDENTITY
+        };
+        for i in 0..9 {
+            ret.rows[rows[i] as usize] = i as u8;
+            ret.cols[cols[i] as usize] = i as u8;
+            ret.digits[self.digits[i] as usize] = i as u8;
+        }
+        ret
+    }
+
+    // The cell of the original grid that ends up in the given cell
+    fn source(&self, cell: usize) -> usize {
+        let row = self.rows[cell / 9] as usize;
+        let col = self.cols[cell % 9] as usize;
+        if self.transpose {
+            col * 9 + row
+        } else {
+            row * 9 + col
+        }
+    }
+
+    pub fn apply(&self, board: &Board) -> Board {
+        let mut ret = Board::new();
+        for (i, state) in ret.state.iter_mut().enumerate() {
+            *state = match board.state[self.source(i)] {
+                CellState::Solved(digit) => CellState::Solved(self.digits[digit as usize]),
+                CellState::Unsolved(possibilities) => {
+                    let mask = (0..9)
+                        .filter(|&digit| possibilities.mask & number_to_mask(digit) != 0)
+                        .fold(0, |mask, digit| {
+                            mask | number_to_mask(self.digits[digit as usize])
+                        });
+                    CellState::Unsolved(Possibilities {
+                        mask,
+                        count: possibilities.count,
+                    })
+                }
+            };
+        }
+        ret
+    }
+}
+
+// Row r of a band taken from band bands[b] is taken from the same row of that band
+fn expand_blocks(blocks: [u8; 3]) -> [u8; 9] {
+    let mut ret = [0; 9];
+    for (i, line) in ret.iter_mut().enumerate() {
+        *line = blocks[i / 3] * 3 + i as u8 % 3;
+    }
+    ret
+}
+
+fn check_line_order(order: &[u8; 9]) -> Result<(), String> {
+    let mut seen = [false; 9];
+    for (i, &line) in order.iter().enumerate() {
+        if line > 8 || seen[line as usize] {
+            return Err("Every line has to be used exactly once".to_string());
+        }
+        seen[line as usize] = true;
+        if line / 3 != order[i / 3 * 3] / 3 {
+            return Err("Lines can only move within their band or stack".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn random_line_order<R: Rng>(rng: &mut R) -> [u8; 9] {
+    let mut blocks = [0, 1, 2];
+    blocks.shuffle(rng);
+    let mut ret = expand_blocks(blocks);
+    for band in ret.chunks_mut(3) {
+        band.shuffle(rng);
+    }
+    ret
+}
+
+// Transformations are written as a list of steps, separated by commas, that are done one after
+// the other: "transpose", "rotate" (clockwise, or "rotate:2" for two quarter turns),
+// "mirror:horizontal" or "mirror:vertical", and "rows", "cols", "bands", "stacks" or "digits"
+// followed by either a swap like ":1-3", or a complete new order like ":312". A new order of
+// digits gives the digit that each one becomes, the others which line each line is taken from.
+impl FromStr for Transform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ret = Transform::IDENTITY;
+        for step in s.split(',').map(str::trim) {
+            let (name, value) = match step.find(':') {
+                Some(i) => (&step[..i], Some(&step[i + 1..])),
+                None => (step, None),
+            };
+            let step = match (name.to_lowercase().as_str(), value) {
+                ("identity", None) => Transform::IDENTITY,
+                ("transpose", None) => Transform::transposed(),
+                ("rotate", None) => Transform::rotated(1),
+                ("rotate", Some(quarters)) => Transform::rotated(
+                    quarters
+                        .parse()
+                        .map_err(|_| format!("'{}' is not a number of quarter turns", quarters))?,
+                ),
+                ("mirror", Some("horizontal")) => Transform::mirrored_horizontally(),
+                ("mirror", Some("vertical")) => Transform::mirrored_vertically(),
+                ("rows", Some(order)) => Transform::reordered_rows(parse_order(order)?)?,
+                ("cols", Some(order)) => Transform::reordered_cols(parse_order(order)?)?,
+                ("bands", Some(order)) => Transform::reordered_bands(parse_order(order)?)?,
+                ("stacks", Some(order)) => Transform::reordered_stacks(parse_order(order)?)?,
+                ("digits", Some(order)) => Transform::relabeled(parse_order(order)?),
+                _ => return Err(format!("Unknown transformation step '{}'", step)),
+            };
+            ret = ret.then(&step);
+        }
+        Ok(ret)
+    }
+}
+
+// Either a swap of two numbers, or all of them in a new order. Numbers start at 1.
+fn parse_order<const N: usize>(s: &str) -> Result<[u8; N], String> {
+    let invalid = || format!("'{}' is neither a swap nor an order of 1 to {}", s, N);
+    let mut ret = [0; N];
+    if let Some((a, b)) = s.split_once('-') {
+        for (i, x) in ret.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let a: usize = a.trim().parse().map_err(|_| invalid())?;
+        let b: usize = b.trim().parse().map_err(|_| invalid())?;
+        if a == 0 || a > N || b == 0 || b > N {
+            return Err(invalid());
+        }
+        ret.swap(a - 1, b - 1);
+        return Ok(ret);
+    }
+    let digits: Vec<u32> = s.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() != N || s.chars().count() != N {
+        return Err(invalid());
+    }
+    let mut seen = [false; N];
+    for (x, &digit) in ret.iter_mut().zip(&digits) {
+        if digit == 0 || digit as usize > N || seen[digit as usize - 1] {
+            return Err(invalid());
+        }
+        seen[digit as usize - 1] = true;
+        *x = digit as u8 - 1;
+    }
+    Ok(ret)
+}
+
+// Writes the transformation as steps that can be read back in
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = |order: &[u8; 9]| {
+            order
+                .iter()
+                .map(|x| (x + 1).to_string())
+                .collect::<String>()
+        };
+        let mut steps = vec![];
+        if self.transpose {
+            steps.push("transpose".to_string());
+        }
+        if self.rows != Transform::IDENTITY.rows {
+            steps.push(format!("rows:{}", order(&self.rows)));
+        }
+        if self.cols != Transform::IDENTITY.cols {
+            steps.push(format!("cols:{}", order(&self.cols)));
+        }
+        if self.digits != Transform::IDENTITY.digits {
+            steps.push(format!("digits:{}", order(&self.digits)));
+        }
+        if steps.is_empty() {
+            steps.push("identity".to_string());
+        }
+        write!(f, "{}", steps.join(","))
+    }
+}
+
+// Every order of the rows (or columns) that keeps the bands (or stacks) together
+fn line_orders() -> Vec<[u8; 9]> {
+    let mut ret = Vec::with_capacity(6 * 6 * 6 * 6);
+    for bands in &PERMUTATIONS_3 {
+        for first in &PERMUTATIONS_3 {
+            for second in &PERMUTATIONS_3 {
+                for third in &PERMUTATIONS_3 {
+                    let mut order = [0; 9];
+                    for (band, lines) in [first, second, third].iter().enumerate() {
+                        for (line, &within) in lines.iter().enumerate() {
+                            order[band * 3 + line] = bands[band] * 3 + within;
+                        }
+                    }
+                    ret.push(order);
+                }
+            }
+        }
+    }
+    ret
+}
+
+// The minlex form of a puzzle or grid: the transformation of it that is the smallest when read
+// as a string of 81 digits, with empty cells as 0. Two puzzles are transformations of each other
+// if and only if they have the same minlex form. Returns a transformation that leads to it.
+pub fn canonical(board: &Board) -> Transform {
+    let nums = board.to_nums();
+    let orders = line_orders();
+    let mut best = [u8::MAX; 81];
+    let mut best_transform = Transform::IDENTITY;
+    let mut current = [0u8; 81];
+    for &transpose in &[false, true] {
+        let grid: Vec<u8> = (0..81)
+            .map(|i| {
+                if transpose {
+                    nums[i % 9 * 9 + i / 9]
+                } else {
+                    nums[i]
+                }
+            })
+            .collect();
+        for rows in &orders {
+            'cols: for cols in &orders {
+                // Digits are relabeled in the order they first appear, which is the smallest
+                // possible labeling for this placement of the cells
+                let mut labels = [0u8; 10];
+                let mut next = 1;
+                let mut smaller = false;
+                for (i, current) in current.iter_mut().enumerate() {
+                    let digit = grid[rows[i / 9] as usize * 9 + cols[i % 9] as usize] as usize;
+                    if digit != 0 && labels[digit] == 0 {
+                        labels[digit] = next;
+                        next += 1;
+                    }
+                    *current = labels[digit];
+                    if !smaller {
+                        if *current > best[i] {
+                            continue 'cols;
+                        }
+                        smaller = *current < best[i];
+                    }
+                }
+                if smaller {
+                    best = current;
+                    // Digits that don't appear get the remaining labels
+                    for label in labels[1..].iter_mut().filter(|label| **label == 0) {
+                        *label = next;
+                        next += 1;
+                    }
+                    let mut digits = [0; 9];
+                    for (digit, label) in digits.iter_mut().enumerate() {
+                        *label = labels[digit + 1] - 1;
+                    }
+                    best_transform = Transform {
+                        transpose,
+                        rows: *rows,
+                        cols: *cols,
+                        digits,
+                    };
+                }
+            }
+        }
+    }
+    best_transform
+}
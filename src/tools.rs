@@ -23,23 +23,6 @@ impl BitWriter {
         }
     }
 
-    pub fn write_u16(&mut self, value: u16, length: u8) {
-        if length <= 8 {
-            self.write((value & 0x00FF) as u8, length);
-        } else {
-            self.write(((value & 0xFF00) >> 8) as u8, 8);
-            self.write((value & 0x00FF) as u8, length - 8);
-        }
-    }
-
-    pub fn disolve(mut self) -> Vec<u8> {
-        if self.pos > 0 {
-            self.bits.push(((self.buffer & 0xFF00) >> 8) as u8);
-            self.pos = 0;
-        }
-        self.bits
-    }
-
     pub fn disolve_drop_zeros(mut self) -> Vec<u8> {
         if self.pos > 0 && self.buffer > 0 {
             self.bits.push(((self.buffer & 0xFF00) >> 8) as u8);